@@ -0,0 +1,342 @@
+//! SSH `authorized_keys` codec
+//!
+//! https://man.openbsd.org/sshd.8#AUTHORIZED_KEYS_FILE_FORMAT
+//!
+//! The value must be an array of keys. Key without options is a string (see the
+//! `ssh-public-key` type), key with options is an object:
+//!
+//! ```yaml
+//! - ssh-ed25519 AAAA... user@host
+//! - key: ssh-rsa AAAA... deploy
+//!   options:
+//!     - no-pty
+//!     - command="/usr/bin/deploy"
+//! ```
+//!
+//! Keys are validated when encoding, so a malformed key can't be written. Comments
+//! are preserved. Original lines are matched with keys by the key data and they're
+//! kept untouched if neither options nor the key comment changed. New keys are appended.
+//!
+//! Lines with well formed keys of unsupported types (`ssh-dss`, certificates, ...) are
+//! decoded as strings containing the whole line & encoded verbatim. It's up to the
+//! validator (`ssh-public-key` items) to reject them.
+use serde_json::{Map, Value};
+
+use crate::{
+    codec::{content_as_str, Codec},
+    error::{Error, Result, ResultExt},
+    utils::ssh::{parse_authorized_key, parse_public_key, KeyError, PublicKey},
+};
+
+const KEY_KEY: &str = "key";
+const OPTIONS_KEY: &str = "options";
+
+/// SSH `authorized_keys` codec
+#[derive(Debug, Default)]
+pub struct AuthorizedKeysCodec;
+
+#[derive(Debug, PartialEq)]
+struct Entry<'a> {
+    options: Vec<&'a str>,
+    key: PublicKey<'a>,
+}
+
+impl<'a> Entry<'a> {
+    fn to_line(&self) -> String {
+        let mut line = String::new();
+        if !self.options.is_empty() {
+            line.push_str(&self.options.join(","));
+            line.push(' ');
+        }
+        line.push_str(self.key.key_type);
+        line.push(' ');
+        line.push_str(self.key.data);
+        if let Some(comment) = self.key.comment {
+            line.push(' ');
+            line.push_str(comment);
+        }
+        line
+    }
+
+    fn to_value(&self) -> Value {
+        let key = Value::String(self.to_line_without_options());
+
+        if self.options.is_empty() {
+            return key;
+        }
+
+        let mut result = Map::new();
+        result.insert(KEY_KEY.to_string(), key);
+        result.insert(
+            OPTIONS_KEY.to_string(),
+            Value::Array(self.options.iter().map(|x| Value::String(x.to_string())).collect()),
+        );
+        Value::Object(result)
+    }
+
+    fn to_line_without_options(&self) -> String {
+        Entry {
+            options: vec![],
+            key: self.key,
+        }
+        .to_line()
+    }
+}
+
+/// Single `authorized_keys` line
+#[derive(Debug, PartialEq)]
+enum Line<'a> {
+    Key(Entry<'a>),
+    /// Line with unsupported key type, kept verbatim
+    Unsupported(&'a str),
+}
+
+impl<'a> Line<'a> {
+    fn to_line(&self) -> String {
+        match self {
+            Line::Key(entry) => entry.to_line(),
+            Line::Unsupported(line) => line.to_string(),
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        match self {
+            Line::Key(entry) => entry.to_value(),
+            Line::Unsupported(line) => Value::String(line.to_string()),
+        }
+    }
+
+    /// Original line & value line represent the same key
+    fn is_same_key(&self, other: &Line) -> bool {
+        match (self, other) {
+            (Line::Key(a), Line::Key(b)) => a.key.data == b.key.data,
+            (Line::Unsupported(a), Line::Unsupported(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+/// Returns line, `None` for empty lines and comments
+fn parse_line(line: &str) -> Result<Option<Line<'_>>> {
+    let line = line.trim();
+
+    if line.is_empty() || line.starts_with('#') {
+        return Ok(None);
+    }
+
+    match parse_authorized_key(line) {
+        Ok((options, key)) => Ok(Some(Line::Key(Entry { options, key }))),
+        Err(KeyError::UnsupportedType(_)) => Ok(Some(Line::Unsupported(line))),
+        Err(e) => Err(Error::with_message("invalid authorized key").context("reason", e.to_string())),
+    }
+}
+
+fn value_to_line(value: &Value) -> Result<Line<'_>> {
+    let (key, options) = match value {
+        Value::String(line) => match parse_line(line) {
+            Ok(Some(line @ Line::Unsupported(_))) => return Ok(line),
+            _ => (line.as_str(), vec![]),
+        },
+        Value::Object(object) => {
+            let key = object.get(KEY_KEY).and_then(Value::as_str).ok_or_else(|| {
+                Error::with_message("missing or non string field").context("field", KEY_KEY.to_string())
+            })?;
+
+            let options = match object.get(OPTIONS_KEY) {
+                None | Some(Value::Null) => vec![],
+                Some(Value::Array(options)) => options
+                    .iter()
+                    .map(|x| x.as_str().ok_or_else(|| Error::with_message("option must be a string")))
+                    .collect::<Result<_>>()?,
+                Some(_) => return Err(Error::with_message("options must be an array")),
+            };
+
+            if let Some(unknown) = object.keys().find(|k| *k != KEY_KEY && *k != OPTIONS_KEY) {
+                return Err(Error::with_message("unexpected field").context("field", unknown.to_string()));
+            }
+
+            (key, options)
+        }
+        _ => return Err(Error::with_message("authorized key must be a string or an object")),
+    };
+
+    let key = parse_public_key(key)
+        .map_err(|e| Error::with_message("invalid ssh public key").context("reason", e.to_string()))?;
+
+    let entry = Entry { options, key };
+
+    // Options must survive the round trip (no unquoted whitespace, commas, ...)
+    match parse_line(&entry.to_line()) {
+        Ok(Some(Line::Key(ref parsed))) if *parsed == entry => Ok(Line::Key(entry)),
+        _ => Err(Error::with_message("invalid key options").context("options", entry.options.join(","))),
+    }
+}
+
+impl Codec for AuthorizedKeysCodec {
+    fn decode(&self, content: &[u8]) -> Result<Value> {
+        let content = content_as_str(content)?;
+
+        let mut result = vec![];
+        for (idx, line) in content.lines().enumerate() {
+            if let Some(line) = parse_line(line).context("line", (idx + 1).to_string())? {
+                result.push(line.to_value());
+            }
+        }
+        Ok(Value::Array(result))
+    }
+
+    fn encode(&self, value: &Value, original: Option<&[u8]>) -> Result<Vec<u8>> {
+        let entries = value
+            .as_array()
+            .ok_or_else(|| Error::with_message("unable to encode non array value"))?
+            .iter()
+            .enumerate()
+            .map(|(idx, x)| value_to_line(x).context("index", idx.to_string()))
+            .collect::<Result<Vec<Line>>>()?;
+        let mut written = vec![false; entries.len()];
+
+        let original = match original {
+            Some(content) => content_as_str(content)?,
+            None => "",
+        };
+
+        let mut lines = vec![];
+
+        for (idx, line) in original.lines().enumerate() {
+            let original_entry = match parse_line(line).context("line", (idx + 1).to_string())? {
+                Some(line) => line,
+                None => {
+                    lines.push(line.to_string());
+                    continue;
+                }
+            };
+
+            let position = entries
+                .iter()
+                .zip(&written)
+                .position(|(e, written)| !written && e.is_same_key(&original_entry));
+
+            // Key was removed
+            let position = match position {
+                Some(position) => position,
+                None => continue,
+            };
+            written[position] = true;
+
+            if entries[position] == original_entry {
+                lines.push(line.to_string());
+            } else {
+                lines.push(entries[position].to_line());
+            }
+        }
+
+        lines.extend(
+            entries
+                .iter()
+                .zip(written)
+                .filter(|(_, written)| !written)
+                .map(|(e, _)| e.to_line()),
+        );
+
+        let mut result = lines.join("\n");
+        if !result.is_empty() {
+            result.push('\n');
+        }
+        Ok(result.into_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const ED25519: &str = "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIGjhy/7vGVEQWpW8VA6XdeW9pJ9pv6TaX7VyDGx8tHUV";
+    const ECDSA: &str = "ecdsa-sha2-nistp256 AAAAE2VjZHNhLXNoYTItbmlzdHAyNTYAAAAIbmlzdHAyNTYAAABBBIrWU2QX9dw/WiWYsm5JgBIN0U1X9cMc5Bvmnkw592jlquwK7FNRDCuu+5T9V7sTruh8cSc8RSM21lMMW1rnRck=";
+    // Blob of the ssh-dss key with dummy parameters
+    const DSS: &str = "ssh-dss AAAAB3NzaC1kc3MAAAABcAAAAAFxAAAAAWcAAAABeQ== legacy";
+
+    fn authorized_keys() -> String {
+        format!(
+            "# Managed keys\n{} user@host\nno-pty,command=\"echo a, b\"  {} deploy\nno-pty {}\n",
+            ED25519, ECDSA, DSS
+        )
+    }
+
+    fn encode(value: &Value) -> String {
+        String::from_utf8(
+            AuthorizedKeysCodec
+                .encode(value, Some(authorized_keys().as_bytes()))
+                .unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn decode() {
+        assert_eq!(
+            AuthorizedKeysCodec.decode(authorized_keys().as_bytes()).unwrap(),
+            json!([
+                format!("{} user@host", ED25519),
+                {"key": format!("{} deploy", ECDSA), "options": ["no-pty", "command=\"echo a, b\""]},
+                format!("no-pty {}", DSS),
+            ])
+        );
+    }
+
+    #[test]
+    fn encode_decoded_value_preserves_content() {
+        let value = AuthorizedKeysCodec.decode(authorized_keys().as_bytes()).unwrap();
+        assert_eq!(encode(&value), authorized_keys());
+    }
+
+    #[test]
+    fn encode_modified_keys() {
+        let value = json!([
+            {"key": format!("{} deploy", ECDSA), "options": ["restrict"]},
+            format!("{} admin", ED25519),
+        ]);
+
+        let expected = format!("# Managed keys\n{} admin\nrestrict {} deploy\n", ED25519, ECDSA);
+        assert_eq!(encode(&value), expected);
+    }
+
+    #[test]
+    fn encode_removes_and_appends_keys() {
+        let value = json!([format!("{} deploy", ECDSA), format!("{} new", ED25519)]);
+
+        let expected = format!("# Managed keys\n{} new\n{} deploy\n", ED25519, ECDSA);
+        assert_eq!(encode(&value), expected);
+    }
+
+    #[test]
+    fn encode_unsupported_keys_verbatim() {
+        let value = json!([format!("no-pty {}", DSS), DSS]);
+
+        let expected = format!("# Managed keys\nno-pty {}\n{}\n", DSS, DSS);
+        assert_eq!(encode(&value), expected);
+    }
+
+    #[test]
+    fn fail_on_invalid_key() {
+        let value = json!(["ssh-dss AAAAB3NzaC1kc3MAAACBAP1 user@host"]);
+        assert!(AuthorizedKeysCodec.encode(&value, None).is_err());
+
+        // Truncated curve point
+        let value = json!(["ecdsa-sha2-nistp256 AAAAE2VjZHNhLXNoYTItbmlzdHAyNTYAAAAIbmlzdHAyNTYAAABBBA=="]);
+        assert!(AuthorizedKeysCodec.encode(&value, None).is_err());
+
+        let value = json!([format!("ssh-rsa {}", &ED25519[12..])]);
+        assert!(AuthorizedKeysCodec.encode(&value, None).is_err());
+    }
+
+    #[test]
+    fn fail_on_invalid_options() {
+        let value = json!([{"key": ED25519, "options": ["command=\"unterminated"]}]);
+        assert!(AuthorizedKeysCodec.encode(&value, None).is_err());
+
+        let value = json!([{"key": ED25519, "options": ["no-pty no-agent-forwarding"]}]);
+        assert!(AuthorizedKeysCodec.encode(&value, None).is_err());
+    }
+}
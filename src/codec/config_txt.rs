@@ -0,0 +1,422 @@
+//! Raspberry Pi `config.txt` codec
+//!
+//! https://www.raspberrypi.org/documentation/configuration/config-txt/
+//!
+//! The value must be an object, where:
+//!
+//! * scalar values are mapped to `key=value` lines
+//! * stringlists are mapped to repeated `key=value` lines (`dtoverlay`, `dtparam`, ...)
+//! * objects are mapped to conditional filter sections (`[pi4]`, `[HDMI:0]`, ...)
+//!
+//! Lines before the first conditional filter and lines after the `[all]` filter belong
+//! to the top level object. All values are decoded as strings, booleans are encoded
+//! as `1` / `0`. Comments and lines we don't understand (`include`, ...) are preserved.
+use std::collections::HashMap;
+
+use serde_json::{Map, Value};
+
+use crate::{
+    codec::{content_as_str, encode_scalar, Codec},
+    error::{Error, Result, ResultExt},
+};
+
+/// Keys which can be repeated and whose values accumulate
+const REPEATABLE_KEYS: &[&str] = &["dtoverlay", "dtparam", "device_tree_overlay", "device_tree_param"];
+
+/// Conditional filter which resets all previous filters
+const ALL_FILTER: &str = "all";
+
+/// Raspberry Pi `config.txt` codec
+#[derive(Debug, Default)]
+pub struct ConfigTxtCodec;
+
+enum Line<'a> {
+    /// Empty line, comment or anything we don't understand
+    Verbatim,
+    /// Conditional filter (`[pi4]`)
+    Filter(&'a str),
+    /// Key & value (`dtoverlay=vc4-kms-v3d`)
+    Entry(&'a str, &'a str),
+}
+
+fn parse_line(line: &str) -> Line<'_> {
+    let line = line.trim();
+
+    if line.is_empty() || line.starts_with('#') {
+        return Line::Verbatim;
+    }
+
+    if line.starts_with('[') && line.ends_with(']') {
+        return Line::Filter(line[1..line.len() - 1].trim());
+    }
+
+    match line.find('=') {
+        Some(idx) => Line::Entry(line[..idx].trim(), line[idx + 1..].trim()),
+        None => Line::Verbatim,
+    }
+}
+
+/// Returns section name for the conditional filter, `None` for the top level
+fn filter_section(filter: &str) -> Option<&str> {
+    if filter.eq_ignore_ascii_case(ALL_FILTER) {
+        None
+    } else {
+        Some(filter)
+    }
+}
+
+fn is_repeatable(key: &str) -> bool {
+    REPEATABLE_KEYS.contains(&key)
+}
+
+fn decode_entry(object: &mut Map<String, Value>, key: &str, value: &str) -> Result<()> {
+    if !is_repeatable(key) {
+        // Last one wins
+        object.insert(key.to_string(), Value::String(value.to_string()));
+        return Ok(());
+    }
+
+    match object
+        .entry(key.to_string())
+        .or_insert_with(|| Value::Array(vec![]))
+        .as_array_mut()
+    {
+        Some(values) => {
+            values.push(Value::String(value.to_string()));
+            Ok(())
+        }
+        None => Err(Error::with_message("conditional filter conflicts with key").context("key", key.to_string())),
+    }
+}
+
+/// Desired values of a single key
+struct Entry {
+    key: String,
+    values: Vec<String>,
+    // Number of values already written to the output
+    written: usize,
+}
+
+impl Entry {
+    fn remaining(&self) -> &[String] {
+        &self.values[self.written..]
+    }
+}
+
+fn encode_entry(key: &str, value: &Value) -> Result<Entry> {
+    if key.is_empty() || key.contains(&['=', '\n', '[', '#'][..]) {
+        return Err(Error::with_message("invalid key").context("key", key.to_string()));
+    }
+
+    let values = match value {
+        Value::Null => vec![],
+        Value::Array(items) if is_repeatable(key) => items
+            .iter()
+            .map(|x| encode_scalar(key, x))
+            .collect::<Result<Vec<String>>>()?,
+        Value::Array(_) => {
+            return Err(Error::with_message("key can't have multiple values").context("key", key.to_string()));
+        }
+        _ => vec![encode_scalar(key, value)?],
+    };
+
+    Ok(Entry {
+        key: key.to_string(),
+        values,
+        written: 0,
+    })
+}
+
+/// Splits the value into top level entries and conditional filter sections
+fn encode_sections(value: &Value) -> Result<Vec<(Option<String>, Vec<Entry>)>> {
+    let object = value
+        .as_object()
+        .ok_or_else(|| Error::with_message("unable to encode non object value"))?;
+
+    let mut top_level = vec![];
+    let mut sections = vec![];
+
+    for (key, value) in object {
+        match value {
+            Value::Object(filter_object) => {
+                if filter_section(key).is_none() || key.contains(&[']', '\n'][..]) {
+                    return Err(Error::with_message("invalid conditional filter").context("filter", key.to_string()));
+                }
+
+                let entries = filter_object
+                    .iter()
+                    .map(|(k, v)| encode_entry(k, v))
+                    .collect::<Result<Vec<Entry>>>()
+                    .context("filter", key.to_string())?;
+
+                sections.push((Some(key.to_string()), entries));
+            }
+            _ => top_level.push(encode_entry(key, value)?),
+        };
+    }
+
+    sections.insert(0, (None, top_level));
+    Ok(sections)
+}
+
+impl Codec for ConfigTxtCodec {
+    fn decode(&self, content: &[u8]) -> Result<Value> {
+        let content = content_as_str(content)?;
+
+        let mut root = Map::new();
+        let mut section: Option<&str> = None;
+
+        for (idx, line) in content.lines().enumerate() {
+            match parse_line(line) {
+                Line::Verbatim => {}
+                Line::Filter(filter) => section = filter_section(filter),
+                Line::Entry(key, value) => {
+                    let object = match section {
+                        None => &mut root,
+                        Some(name) => root
+                            .entry(name.to_string())
+                            .or_insert_with(|| Value::Object(Map::new()))
+                            .as_object_mut()
+                            .ok_or_else(|| {
+                                Error::with_message("key conflicts with conditional filter")
+                                    .context("filter", name.to_string())
+                            })?,
+                    };
+
+                    decode_entry(object, key, value).context("line", (idx + 1).to_string())?;
+                }
+            };
+        }
+
+        Ok(Value::Object(root))
+    }
+
+    fn encode(&self, value: &Value, original: Option<&[u8]>) -> Result<Vec<u8>> {
+        let mut sections = encode_sections(value)?;
+
+        let original = match original {
+            Some(content) => content_as_str(content)?,
+            None => "",
+        };
+        let lines: Vec<&str> = original.lines().collect();
+
+        // slots[0] is the beginning of the file, slots[i + 1] contains the original line i
+        // replacement followed by lines inserted after it
+        let mut slots: Vec<Vec<String>> = vec![vec![]; lines.len() + 1];
+        // Slot of the last filter or entry line for every section
+        let mut section_slots: HashMap<Option<&str>, usize> = HashMap::new();
+        section_slots.insert(None, 0);
+        // Slot of the last line for every (section, key)
+        let mut key_slots: HashMap<(Option<&str>, &str), usize> = HashMap::new();
+
+        let mut current: Option<&str> = None;
+
+        for (idx, line) in lines.iter().enumerate() {
+            let slot = idx + 1;
+
+            match parse_line(line) {
+                Line::Verbatim => {
+                    slots[slot].push(line.to_string());
+                    continue;
+                }
+                Line::Filter(filter) => {
+                    current = filter_section(filter);
+                    slots[slot].push(line.to_string());
+                }
+                Line::Entry(key, _) => {
+                    let entry = sections
+                        .iter_mut()
+                        .find(|(name, _)| name.as_ref().map(String::as_str) == current)
+                        .and_then(|(_, entries)| entries.iter_mut().find(|e| e.key == key));
+
+                    // Replace the value or drop the line if there're no values left
+                    if let Some(entry) = entry {
+                        if let Some(value) = entry.remaining().first() {
+                            slots[slot].push(format!("{}={}", key, value));
+                            entry.written += 1;
+                        }
+                    }
+
+                    key_slots.insert((current, key), slot);
+                }
+            };
+
+            section_slots.insert(current, slot);
+        }
+
+        let mut appended = vec![];
+
+        for (name, entries) in &sections {
+            let section = name.as_ref().map(String::as_str);
+
+            let section_slot = match section_slots.get(&section) {
+                Some(slot) => *slot,
+                None => {
+                    // New conditional filter, append it to the end of the file
+                    if entries.iter().any(|e| !e.remaining().is_empty()) {
+                        appended.push(format!("[{}]", section.expect("top level section always exists")));
+                        for entry in entries {
+                            appended.extend(entry.remaining().iter().map(|v| format!("{}={}", entry.key, v)));
+                        }
+                    }
+                    continue;
+                }
+            };
+
+            for entry in entries {
+                let slot = key_slots
+                    .get(&(section, entry.key.as_str()))
+                    .cloned()
+                    .unwrap_or(section_slot);
+                slots[slot].extend(entry.remaining().iter().map(|v| format!("{}={}", entry.key, v)));
+            }
+        }
+
+        let mut output: Vec<String> = slots.into_iter().flatten().collect();
+        output.extend(appended);
+
+        let mut result = output.join("\n");
+        if !result.is_empty() {
+            result.push('\n');
+        }
+        Ok(result.into_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const CONFIG_TXT: &str = r#"# Raspberry Pi config
+gpu_mem=16
+dtoverlay=vc4-kms-v3d
+dtparam=i2c_arm=on
+dtparam=spi=on
+
+[pi4]
+# Pi 4 only
+arm_boost=1
+
+[all]
+disable_splash=1
+"#;
+
+    fn decode(content: &str) -> Value {
+        ConfigTxtCodec.decode(content.as_bytes()).unwrap()
+    }
+
+    fn encode(value: &Value, original: &str) -> String {
+        String::from_utf8(ConfigTxtCodec.encode(value, Some(original.as_bytes())).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn decode_sections_and_repeated_keys() {
+        assert_eq!(
+            decode(CONFIG_TXT),
+            json!({
+                "gpu_mem": "16",
+                "dtoverlay": ["vc4-kms-v3d"],
+                "dtparam": ["i2c_arm=on", "spi=on"],
+                "disable_splash": "1",
+                "pi4": {
+                    "arm_boost": "1"
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn decode_last_one_wins() {
+        assert_eq!(decode("gpu_mem=16\ngpu_mem=32\n"), json!({"gpu_mem": "32"}));
+    }
+
+    #[test]
+    fn encode_decoded_value_preserves_content() {
+        let value = decode(CONFIG_TXT);
+        assert_eq!(encode(&value, CONFIG_TXT), CONFIG_TXT);
+    }
+
+    #[test]
+    fn encode_replaces_values_in_place() {
+        let mut value = decode(CONFIG_TXT);
+        value["gpu_mem"] = json!(128);
+        value["pi4"]["arm_boost"] = json!(false);
+
+        let expected = CONFIG_TXT
+            .replace("gpu_mem=16", "gpu_mem=128")
+            .replace("arm_boost=1", "arm_boost=0");
+        assert_eq!(encode(&value, CONFIG_TXT), expected);
+    }
+
+    #[test]
+    fn encode_repeated_keys() {
+        let mut value = decode(CONFIG_TXT);
+        value["dtparam"] = json!(["audio=on"]);
+        value["dtoverlay"] = json!(["vc4-kms-v3d", "disable-bt"]);
+
+        let expected = CONFIG_TXT
+            .replace(
+                "dtoverlay=vc4-kms-v3d\n",
+                "dtoverlay=vc4-kms-v3d\ndtoverlay=disable-bt\n",
+            )
+            .replace("dtparam=i2c_arm=on\ndtparam=spi=on\n", "dtparam=audio=on\n");
+        assert_eq!(encode(&value, CONFIG_TXT), expected);
+    }
+
+    #[test]
+    fn encode_removes_missing_keys() {
+        let mut value = decode(CONFIG_TXT);
+        value.as_object_mut().unwrap().remove("gpu_mem");
+        value["pi4"] = json!({});
+
+        let expected = CONFIG_TXT.replace("gpu_mem=16\n", "").replace("arm_boost=1\n", "");
+        assert_eq!(encode(&value, CONFIG_TXT), expected);
+    }
+
+    #[test]
+    fn encode_appends_new_keys_to_section() {
+        let mut value = decode(CONFIG_TXT);
+        value["enable_uart"] = json!(true);
+        value["pi4"]["arm_freq"] = json!(2000);
+
+        let expected = CONFIG_TXT
+            .replace("disable_splash=1\n", "disable_splash=1\nenable_uart=1\n")
+            .replace("arm_boost=1\n", "arm_boost=1\narm_freq=2000\n");
+        assert_eq!(encode(&value, CONFIG_TXT), expected);
+    }
+
+    #[test]
+    fn encode_appends_new_filter() {
+        let mut value = decode(CONFIG_TXT);
+        value["pi0"] = json!({"dtoverlay": ["dwc2"]});
+
+        let expected = format!("{}[pi0]\ndtoverlay=dwc2\n", CONFIG_TXT);
+        assert_eq!(encode(&value, CONFIG_TXT), expected);
+    }
+
+    #[test]
+    fn encode_without_original() {
+        let value = json!({
+            "dtoverlay": ["vc4-kms-v3d", "disable-bt"]
+        });
+        let encoded = ConfigTxtCodec.encode(&value, None).unwrap();
+        assert_eq!(
+            String::from_utf8(encoded).unwrap(),
+            "dtoverlay=vc4-kms-v3d\ndtoverlay=disable-bt\n"
+        );
+    }
+
+    #[test]
+    fn fail_on_multiple_values_for_non_repeatable_key() {
+        let value = json!({"gpu_mem": ["16", "32"]});
+        assert!(ConfigTxtCodec.encode(&value, None).is_err());
+    }
+
+    #[test]
+    fn fail_on_nested_filter() {
+        let value = json!({"pi4": {"pi3": {"foo": "bar"}}});
+        assert!(ConfigTxtCodec.encode(&value, None).is_err());
+    }
+}
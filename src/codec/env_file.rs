@@ -0,0 +1,355 @@
+//! Environment file (`KEY=VALUE`) codec
+//!
+//! Covers `/etc/default/*` like files, which are sourced by shell scripts or
+//! read by systemd `EnvironmentFile=`.
+//!
+//! The value must be a flat object, all values are decoded as strings. Decoding
+//! understands the `export` prefix, single quotes, double quotes, backslash escapes
+//! and trailing comments. Variables are not expanded. Values which need quoting are
+//! encoded in double quotes, which is understood by both, shell and systemd.
+//! Comments are preserved and unchanged values keep their original quoting. Only the value
+//! of a changed line is replaced, the `export` prefix & trailing comment are kept.
+use std::collections::HashSet;
+use std::ops::Range;
+
+use serde_json::{Map, Value};
+
+use crate::{
+    codec::{content_as_str, encode_scalar, Codec},
+    error::{Error, Result, ResultExt},
+};
+
+/// Environment file codec
+#[derive(Debug, Default)]
+pub struct EnvFileCodec;
+
+fn is_valid_key(key: &str) -> bool {
+    let mut chars = key.chars();
+
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => chars.all(|c| c.is_ascii_alphanumeric() || c == '_'),
+        _ => false,
+    }
+}
+
+fn unterminated(quote: &str) -> Error {
+    Error::with_message("unterminated quoted string").context("quote", quote.to_string())
+}
+
+/// Removes quotes & escapes from the raw value, returns the value & the raw value length
+///
+/// The raw value ends with the first unquoted whitespace (trailing whitespace & comment).
+fn unquote(raw: &str) -> Result<(String, usize)> {
+    let mut result = String::new();
+    let mut chars = raw.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => loop {
+                match chars.next() {
+                    Some('\'') => break,
+                    Some(c) => result.push(c),
+                    None => return Err(unterminated("'")),
+                };
+            },
+            '"' => loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some('\\') => match chars.next() {
+                        Some(c) if "\"\\$`".contains(c) => result.push(c),
+                        Some(c) => {
+                            result.push('\\');
+                            result.push(c);
+                        }
+                        None => return Err(unterminated("\"")),
+                    },
+                    Some(c) => result.push(c),
+                    None => return Err(unterminated("\"")),
+                };
+            },
+            '\\' => match chars.next() {
+                Some(c) => result.push(c),
+                None => return Err(Error::with_message("line continuation is not supported")),
+            },
+            c if c.is_whitespace() => {
+                // Only trailing whitespace & comment can follow the value
+                let rest = chars.as_str().trim_start();
+                if rest.is_empty() || rest.starts_with('#') {
+                    return Ok((result, raw.len() - chars.as_str().len() - c.len_utf8()));
+                }
+                return Err(Error::with_message("unquoted whitespace in value"));
+            }
+            c => result.push(c),
+        };
+    }
+
+    Ok((result, raw.len()))
+}
+
+/// Encodes value, quotes it only if it's required
+fn quote(value: &str) -> String {
+    let is_safe = |c: char| c.is_ascii_alphanumeric() || "_-.,:/@%+=".contains(c);
+
+    if !value.is_empty() && value.chars().all(is_safe) {
+        return value.to_string();
+    }
+
+    let mut result = String::with_capacity(value.len() + 2);
+    result.push('"');
+    for c in value.chars() {
+        if "\"\\$`".contains(c) {
+            result.push('\\');
+        }
+        result.push(c);
+    }
+    result.push('"');
+    result
+}
+
+/// Parsed `KEY=VALUE` line
+struct Entry<'a> {
+    key: &'a str,
+    value: String,
+    /// Raw value position in the line, the `export` prefix & trailing comment are outside
+    raw: Range<usize>,
+}
+
+/// Returns parsed entry, `None` for empty lines and comments
+fn parse_line(line: &str) -> Result<Option<Entry<'_>>> {
+    // Trailing whitespace is handled by `unquote`, raw value position must be kept
+    let trimmed = line.trim_start();
+
+    if trimmed.trim_end().is_empty() || trimmed.starts_with('#') {
+        return Ok(None);
+    }
+
+    let trimmed = match trimmed.strip_prefix("export ") {
+        Some(rest) => rest.trim_start(),
+        None => trimmed,
+    };
+
+    let idx = trimmed
+        .find('=')
+        .ok_or_else(|| Error::with_message("expected KEY=VALUE"))?;
+
+    let key = &trimmed[..idx];
+    if !is_valid_key(key) {
+        return Err(Error::with_message("invalid variable name").context("key", key.to_string()));
+    }
+
+    let raw = &trimmed[idx + 1..];
+    let (value, len) = unquote(raw)?;
+    let start = line.len() - raw.len();
+
+    Ok(Some(Entry {
+        key,
+        value,
+        raw: start..start + len,
+    }))
+}
+
+/// Original line & parsed entry (if any)
+type ParsedLine<'a> = (&'a str, Option<Entry<'a>>);
+
+fn parse_lines(content: &str) -> Result<Vec<ParsedLine<'_>>> {
+    content
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| {
+            parse_line(line)
+                .map(|x| (line, x))
+                .context("line", (idx + 1).to_string())
+        })
+        .collect()
+}
+
+impl Codec for EnvFileCodec {
+    fn decode(&self, content: &[u8]) -> Result<Value> {
+        let content = content_as_str(content)?;
+
+        let mut result = Map::new();
+        for (_, entry) in parse_lines(content)? {
+            if let Some(entry) = entry {
+                // Last one wins, same as in shell
+                result.insert(entry.key.to_string(), Value::String(entry.value));
+            }
+        }
+        Ok(Value::Object(result))
+    }
+
+    fn encode(&self, value: &Value, original: Option<&[u8]>) -> Result<Vec<u8>> {
+        let object = value
+            .as_object()
+            .ok_or_else(|| Error::with_message("unable to encode non object value"))?;
+
+        let mut entries = vec![];
+        for (key, value) in object {
+            if !is_valid_key(key) {
+                return Err(Error::with_message("invalid variable name").context("key", key.to_string()));
+            }
+
+            if !value.is_null() {
+                entries.push((key.as_str(), encode_scalar(key, value)?));
+            }
+        }
+
+        let original = match original {
+            Some(content) => content_as_str(content)?,
+            None => "",
+        };
+        let original_lines = parse_lines(original)?;
+
+        let mut written = HashSet::new();
+        let mut lines = vec![];
+
+        for (idx, (line, entry)) in original_lines.iter().enumerate() {
+            let entry = match entry {
+                Some(x) => x,
+                None => {
+                    lines.push(line.to_string());
+                    continue;
+                }
+            };
+
+            // Replace the first occurrence, drop the others
+            if !written.insert(entry.key) {
+                continue;
+            }
+
+            let value = match entries.iter().find(|(k, _)| *k == entry.key) {
+                Some((_, value)) => value,
+                None => continue,
+            };
+
+            let current = original_lines[idx..]
+                .iter()
+                .filter_map(|(_, entry)| entry.as_ref())
+                .rfind(|x| x.key == entry.key)
+                .map(|x| &x.value);
+
+            if current == Some(value) {
+                lines.push(line.to_string());
+            } else {
+                // Only the value is replaced, `export` prefix & trailing comment are kept
+                lines.push(format!(
+                    "{}{}{}",
+                    &line[..entry.raw.start],
+                    quote(value),
+                    &line[entry.raw.end..]
+                ));
+            }
+        }
+
+        lines.extend(
+            entries
+                .iter()
+                .filter(|(k, _)| !written.contains(k))
+                .map(|(k, v)| format!("{}={}", k, quote(v))),
+        );
+
+        let mut result = lines.join("\n");
+        if !result.is_empty() {
+            result.push('\n');
+        }
+        Ok(result.into_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const ENV_FILE: &str = r#"# Defaults for the service
+OPTS="--verbose --port 8080"
+export LOG_LEVEL=info # trailing comment
+GREETING='it'\''s "quoted"'
+EMPTY=
+"#;
+
+    fn encode(value: &Value, original: &str) -> String {
+        String::from_utf8(EnvFileCodec.encode(value, Some(original.as_bytes())).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn decode() {
+        let value = EnvFileCodec.decode(ENV_FILE.as_bytes()).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "OPTS": "--verbose --port 8080",
+                "LOG_LEVEL": "info",
+                "GREETING": "it's \"quoted\"",
+                "EMPTY": ""
+            })
+        );
+    }
+
+    #[test]
+    fn decode_escapes() {
+        let value = EnvFileCodec.decode(b"A=\"\\$HOME \\\"x\\\" \\n\"\nB=a\\ b\n").unwrap();
+        assert_eq!(value, json!({"A": "$HOME \"x\" \\n", "B": "a b"}));
+    }
+
+    #[test]
+    fn encode_decoded_value_preserves_content() {
+        let value = EnvFileCodec.decode(ENV_FILE.as_bytes()).unwrap();
+        assert_eq!(encode(&value, ENV_FILE), ENV_FILE);
+    }
+
+    #[test]
+    fn encode_quotes_values() {
+        let value = json!({
+            "PLAIN": "/usr/bin/foo",
+            "SPACES": "a b",
+            "SPECIAL": "$HOME \"`x`\\",
+            "EMPTY": "",
+        });
+        let encoded = String::from_utf8(EnvFileCodec.encode(&value, None).unwrap()).unwrap();
+        assert_eq!(
+            encoded,
+            "EMPTY=\"\"\nPLAIN=/usr/bin/foo\nSPACES=\"a b\"\nSPECIAL=\"\\$HOME \\\"\\`x\\`\\\\\"\n"
+        );
+        assert_eq!(EnvFileCodec.decode(encoded.as_bytes()).unwrap(), value);
+    }
+
+    #[test]
+    fn encode_replaces_changed_values() {
+        let mut value = EnvFileCodec.decode(ENV_FILE.as_bytes()).unwrap();
+        value["LOG_LEVEL"] = json!("debug level");
+        value["EMPTY"] = Value::Null;
+        value["NEW"] = json!(10);
+
+        let expected = ENV_FILE
+            .replace(
+                "export LOG_LEVEL=info # trailing comment",
+                "export LOG_LEVEL=\"debug level\" # trailing comment",
+            )
+            .replace("EMPTY=\n", "NEW=10\n");
+        assert_eq!(encode(&value, ENV_FILE), expected);
+    }
+
+    #[test]
+    fn encode_replaces_quoted_value_only() {
+        let original = "  export  FOO='a b'   # comment\nBAR=\"x\"\n";
+        let value = json!({"FOO": "c", "BAR": "y z"});
+        assert_eq!(encode(&value, original), "  export  FOO=c   # comment\nBAR=\"y z\"\n");
+    }
+
+    #[test]
+    fn fail_on_invalid_key() {
+        assert!(EnvFileCodec.decode(b"1FOO=bar\n").is_err());
+        assert!(EnvFileCodec.encode(&json!({"FOO-BAR": "baz"}), None).is_err());
+    }
+
+    #[test]
+    fn fail_on_unquoted_whitespace() {
+        assert!(EnvFileCodec.decode(b"FOO=bar baz\n").is_err());
+    }
+
+    #[test]
+    fn fail_on_unterminated_quote() {
+        assert!(EnvFileCodec.decode(b"FOO=\"bar\n").is_err());
+    }
+}
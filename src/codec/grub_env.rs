@@ -0,0 +1,232 @@
+//! GRUB environment block codec
+//!
+//! https://www.gnu.org/software/grub/manual/grub/html_node/Environment-block.html
+//!
+//! The environment block is a file of exactly 1024 bytes, which starts with the
+//! `# GRUB Environment Block` line, continues with `key=value` lines and is padded
+//! with `#` characters. Backslash and new line characters in values are escaped
+//! with a backslash (same as `grub-editenv`), values can span multiple lines.
+//!
+//! The value must be a flat object, all values are decoded as strings. Encoding keeps
+//! the order of variables from the original content and appends new ones.
+use serde_json::{Map, Value};
+
+use crate::{
+    codec::{content_as_str, encode_multiline_scalar, Codec},
+    error::{Error, Result},
+};
+
+const HEADER: &str = "# GRUB Environment Block\n";
+const BLOCK_SIZE: usize = 1024;
+const PADDING: &str = "#";
+
+/// GRUB environment block codec
+#[derive(Debug, Default)]
+pub struct GrubEnvCodec;
+
+fn escape(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    for c in value.chars() {
+        if c == '\\' || c == '\n' {
+            result.push('\\');
+        }
+        result.push(c);
+    }
+    result
+}
+
+/// Splits the block into unescaped `key=value` entries
+fn entries(block: &str) -> Vec<String> {
+    let mut result = vec![];
+    let mut current = String::new();
+    let mut chars = block.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                if let Some(escaped) = chars.next() {
+                    current.push(escaped);
+                }
+            }
+            '\n' => result.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        };
+    }
+
+    if !current.is_empty() {
+        result.push(current);
+    }
+
+    result
+}
+
+fn decode_entries(content: &[u8]) -> Result<Vec<(String, String)>> {
+    if content.len() != BLOCK_SIZE {
+        return Err(Error::with_message("invalid GRUB environment block size")
+            .context("size", content.len().to_string())
+            .context("expected", BLOCK_SIZE.to_string()));
+    }
+
+    let content = content_as_str(content)?;
+
+    if !content.starts_with(HEADER) {
+        return Err(Error::with_message("missing GRUB environment block header"));
+    }
+
+    let mut result = vec![];
+
+    // Comments & padding are skipped, values (passwords, ...) are not part of errors
+    for (position, entry) in entries(&content[HEADER.len()..])
+        .into_iter()
+        .enumerate()
+        .filter(|(_, x)| !x.is_empty() && !x.starts_with(PADDING))
+    {
+        let idx = entry.find('=').ok_or_else(|| {
+            Error::with_message("invalid environment block entry").context("entry", (position + 1).to_string())
+        })?;
+        result.push((entry[..idx].to_string(), entry[idx + 1..].to_string()));
+    }
+
+    Ok(result)
+}
+
+impl Codec for GrubEnvCodec {
+    fn decode(&self, content: &[u8]) -> Result<Value> {
+        let mut result = Map::new();
+        for (key, value) in decode_entries(content)? {
+            result.insert(key, Value::String(value));
+        }
+        Ok(Value::Object(result))
+    }
+
+    fn encode(&self, value: &Value, original: Option<&[u8]>) -> Result<Vec<u8>> {
+        let object = value
+            .as_object()
+            .ok_or_else(|| Error::with_message("unable to encode non object value"))?;
+
+        let mut keys: Vec<String> = match original {
+            Some(content) => decode_entries(content)?.into_iter().map(|(k, _)| k).collect(),
+            None => vec![],
+        };
+        keys.retain(|k| object.contains_key(k));
+        keys.extend(object.keys().filter(|k| !keys.contains(k)).cloned().collect::<Vec<_>>());
+
+        let mut block = HEADER.to_string();
+
+        for key in keys {
+            let value = &object[&key];
+
+            if value.is_null() {
+                continue;
+            }
+
+            if key.is_empty() || key.contains(&['=', '\n', '\\'][..]) || key.starts_with(PADDING) {
+                return Err(Error::with_message("invalid variable name").context("key", key.to_string()));
+            }
+
+            block.push_str(&key);
+            block.push('=');
+            block.push_str(&escape(&encode_multiline_scalar(&key, value)?));
+            block.push('\n');
+        }
+
+        if block.len() > BLOCK_SIZE {
+            return Err(
+                Error::with_message("variables don't fit into the GRUB environment block")
+                    .context("size", BLOCK_SIZE.to_string())
+                    .context("required", block.len().to_string()),
+            );
+        }
+
+        let padding = BLOCK_SIZE - block.len();
+        block.push_str(&PADDING.repeat(padding));
+
+        Ok(block.into_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn block(entries: &str) -> Vec<u8> {
+        let mut result = format!("{}{}", HEADER, entries);
+        let padding = BLOCK_SIZE - result.len();
+        result.push_str(&PADDING.repeat(padding));
+        result.into_bytes()
+    }
+
+    #[test]
+    fn decode() {
+        let value = GrubEnvCodec
+            .decode(&block("saved_entry=0\nnext_entry=a\\\\b\n"))
+            .unwrap();
+        assert_eq!(value, json!({"saved_entry": "0", "next_entry": "a\\b"}));
+    }
+
+    #[test]
+    fn encode_preserves_order() {
+        let original = block("upgrade_available=0\nbootcount=1\n");
+        let value = json!({"bootcount": 2, "upgrade_available": true, "resin_root_part": "3"});
+
+        let encoded = GrubEnvCodec.encode(&value, Some(&original)).unwrap();
+        assert_eq!(encoded, block("upgrade_available=1\nbootcount=2\nresin_root_part=3\n"));
+    }
+
+    #[test]
+    fn encode_escapes_values() {
+        let value = json!({"path": "C:\\grub"});
+        let encoded = GrubEnvCodec.encode(&value, None).unwrap();
+        assert_eq!(encoded, block("path=C:\\\\grub\n"));
+        assert_eq!(GrubEnvCodec.decode(&encoded).unwrap(), value);
+    }
+
+    #[test]
+    fn encode_escapes_new_lines() {
+        let value = json!({"kernel_args": "quiet\nsplash"});
+        let encoded = GrubEnvCodec.encode(&value, None).unwrap();
+        assert_eq!(encoded, block("kernel_args=quiet\\\nsplash\n"));
+        assert_eq!(GrubEnvCodec.decode(&encoded).unwrap(), value);
+    }
+
+    #[test]
+    fn encode_removes_null_values() {
+        let original = block("upgrade_available=0\nbootcount=1\n");
+        let value = json!({"bootcount": null, "upgrade_available": "0"});
+
+        let encoded = GrubEnvCodec.encode(&value, Some(&original)).unwrap();
+        assert_eq!(encoded, block("upgrade_available=0\n"));
+    }
+
+    #[test]
+    fn fail_on_overflow() {
+        let value = json!({ "kernel_args": "x".repeat(BLOCK_SIZE) });
+        assert!(GrubEnvCodec.encode(&value, None).is_err());
+    }
+
+    #[test]
+    fn fail_on_missing_header() {
+        let mut content = block("saved_entry=0\n");
+        content[0] = b'%';
+        assert!(GrubEnvCodec.decode(&content).is_err());
+    }
+
+    #[test]
+    fn fail_on_invalid_entry_without_value() {
+        let error = GrubEnvCodec.decode(&block("saved_entry=0\nhunter2\n")).unwrap_err();
+        assert!(!format!("{:?}", error).contains("hunter2"));
+    }
+
+    #[test]
+    fn fail_on_invalid_size() {
+        assert!(GrubEnvCodec
+            .decode(format!("{}saved_entry=0\n", HEADER).as_bytes())
+            .is_err());
+
+        let mut content = block("saved_entry=0\n");
+        content.push(b'#');
+        assert!(GrubEnvCodec.decode(&content).is_err());
+    }
+}
@@ -0,0 +1,257 @@
+//! `/etc/hosts` codec
+//!
+//! The value must be an array of host entries (see the `host-entry` type):
+//!
+//! ```yaml
+//! - address: 127.0.0.1
+//!   hostname: localhost
+//!   aliases:
+//!     - localhost.localdomain
+//! ```
+//!
+//! `aliases` are optional. Comments are preserved. Original lines are matched with
+//! entries by address & hostname and they're kept untouched (including trailing
+//! comments) if aliases didn't change. New entries are appended.
+use serde_json::{Map, Value};
+
+use crate::{
+    codec::{content_as_str, Codec},
+    error::{Error, Result, ResultExt},
+};
+
+const ADDRESS_KEY: &str = "address";
+const HOSTNAME_KEY: &str = "hostname";
+const ALIASES_KEY: &str = "aliases";
+
+/// `/etc/hosts` codec
+#[derive(Debug, Default)]
+pub struct HostsCodec;
+
+#[derive(Debug, PartialEq)]
+struct Entry<'a> {
+    address: &'a str,
+    hostname: &'a str,
+    aliases: Vec<&'a str>,
+}
+
+impl<'a> Entry<'a> {
+    fn to_line(&self) -> String {
+        let mut line = format!("{}\t{}", self.address, self.hostname);
+        for alias in &self.aliases {
+            line.push(' ');
+            line.push_str(alias);
+        }
+        line
+    }
+
+    fn to_value(&self) -> Value {
+        let mut result = Map::new();
+        result.insert(ADDRESS_KEY.to_string(), Value::String(self.address.to_string()));
+        result.insert(HOSTNAME_KEY.to_string(), Value::String(self.hostname.to_string()));
+        if !self.aliases.is_empty() {
+            result.insert(
+                ALIASES_KEY.to_string(),
+                Value::Array(self.aliases.iter().map(|x| Value::String(x.to_string())).collect()),
+            );
+        }
+        Value::Object(result)
+    }
+}
+
+/// Returns entry, `None` for empty lines and comments
+fn parse_line(line: &str) -> Result<Option<Entry<'_>>> {
+    let data = match line.find('#') {
+        Some(idx) => &line[..idx],
+        None => line,
+    };
+
+    let mut fields = data.split_whitespace();
+
+    let address = match fields.next() {
+        Some(address) => address,
+        None => return Ok(None),
+    };
+
+    let hostname = fields
+        .next()
+        .ok_or_else(|| Error::with_message("missing hostname").context("address", address.to_string()))?;
+
+    Ok(Some(Entry {
+        address,
+        hostname,
+        aliases: fields.collect(),
+    }))
+}
+
+fn field<'a>(object: &'a Map<String, Value>, key: &str) -> Result<&'a str> {
+    let field = object
+        .get(key)
+        .and_then(Value::as_str)
+        .ok_or_else(|| Error::with_message("missing or non string field").context("field", key.to_string()))?;
+
+    if field.is_empty() || field.contains(|c: char| c.is_whitespace() || c == '#') {
+        return Err(Error::with_message("invalid field value").context("field", key.to_string()));
+    }
+
+    Ok(field)
+}
+
+fn value_to_entry(value: &Value) -> Result<Entry<'_>> {
+    let object = value
+        .as_object()
+        .ok_or_else(|| Error::with_message("host entry must be an object"))?;
+
+    let aliases = match object.get(ALIASES_KEY) {
+        None | Some(Value::Null) => vec![],
+        Some(Value::Array(aliases)) => aliases
+            .iter()
+            .map(|x| match x {
+                Value::String(s) if !s.is_empty() && !s.contains(|c: char| c.is_whitespace() || c == '#') => {
+                    Ok(s.as_str())
+                }
+                _ => Err(Error::with_message("invalid alias")),
+            })
+            .collect::<Result<_>>()?,
+        Some(_) => return Err(Error::with_message("aliases must be an array")),
+    };
+
+    Ok(Entry {
+        address: field(object, ADDRESS_KEY)?,
+        hostname: field(object, HOSTNAME_KEY)?,
+        aliases,
+    })
+}
+
+impl Codec for HostsCodec {
+    fn decode(&self, content: &[u8]) -> Result<Value> {
+        let content = content_as_str(content)?;
+
+        let mut result = vec![];
+        for (idx, line) in content.lines().enumerate() {
+            if let Some(entry) = parse_line(line).context("line", (idx + 1).to_string())? {
+                result.push(entry.to_value());
+            }
+        }
+        Ok(Value::Array(result))
+    }
+
+    fn encode(&self, value: &Value, original: Option<&[u8]>) -> Result<Vec<u8>> {
+        let entries = value
+            .as_array()
+            .ok_or_else(|| Error::with_message("unable to encode non array value"))?
+            .iter()
+            .enumerate()
+            .map(|(idx, x)| value_to_entry(x).context("index", idx.to_string()))
+            .collect::<Result<Vec<Entry>>>()?;
+        let mut written = vec![false; entries.len()];
+
+        let original = match original {
+            Some(content) => content_as_str(content)?,
+            None => "",
+        };
+
+        let mut lines = vec![];
+
+        for (idx, line) in original.lines().enumerate() {
+            let original_entry = match parse_line(line).context("line", (idx + 1).to_string())? {
+                Some(entry) => entry,
+                None => {
+                    lines.push(line.to_string());
+                    continue;
+                }
+            };
+
+            let position = entries.iter().zip(&written).position(|(e, written)| {
+                !written && e.address == original_entry.address && e.hostname == original_entry.hostname
+            });
+
+            // Entry was removed
+            let position = match position {
+                Some(position) => position,
+                None => continue,
+            };
+            written[position] = true;
+
+            if entries[position] == original_entry {
+                lines.push(line.to_string());
+            } else {
+                lines.push(entries[position].to_line());
+            }
+        }
+
+        lines.extend(
+            entries
+                .iter()
+                .zip(written)
+                .filter(|(_, written)| !written)
+                .map(|(e, _)| e.to_line()),
+        );
+
+        let mut result = lines.join("\n");
+        if !result.is_empty() {
+            result.push('\n');
+        }
+        Ok(result.into_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const HOSTS: &str = r#"# Static host entries
+127.0.0.1	localhost localhost.localdomain # loopback
+::1	localhost ip6-localhost
+10.0.0.2	printer
+"#;
+
+    fn encode(value: &Value) -> String {
+        String::from_utf8(HostsCodec.encode(value, Some(HOSTS.as_bytes())).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn decode() {
+        assert_eq!(
+            HostsCodec.decode(HOSTS.as_bytes()).unwrap(),
+            json!([
+                {"address": "127.0.0.1", "hostname": "localhost", "aliases": ["localhost.localdomain"]},
+                {"address": "::1", "hostname": "localhost", "aliases": ["ip6-localhost"]},
+                {"address": "10.0.0.2", "hostname": "printer"},
+            ])
+        );
+    }
+
+    #[test]
+    fn encode_decoded_value_preserves_content() {
+        let value = HostsCodec.decode(HOSTS.as_bytes()).unwrap();
+        assert_eq!(encode(&value), HOSTS);
+    }
+
+    #[test]
+    fn encode_modified_entries() {
+        let value = json!([
+            {"address": "127.0.0.1", "hostname": "localhost", "aliases": ["localhost.localdomain"]},
+            {"address": "::1", "hostname": "localhost"},
+            {"address": "10.0.0.3", "hostname": "scanner", "aliases": ["scanner.lan"]},
+        ]);
+
+        let expected = HOSTS
+            .replace("::1\tlocalhost ip6-localhost\n", "::1\tlocalhost\n")
+            .replace("10.0.0.2\tprinter\n", "10.0.0.3\tscanner scanner.lan\n");
+        assert_eq!(encode(&value), expected);
+    }
+
+    #[test]
+    fn fail_on_missing_hostname() {
+        assert!(HostsCodec.decode(b"10.0.0.2\n").is_err());
+        assert!(HostsCodec.encode(&json!([{"address": "10.0.0.2"}]), None).is_err());
+    }
+
+    #[test]
+    fn fail_on_invalid_hostname() {
+        let value = json!([{"address": "10.0.0.2", "hostname": "foo bar"}]);
+        assert!(HostsCodec.encode(&value, None).is_err());
+    }
+}
@@ -0,0 +1,69 @@
+//! JSON codec
+//!
+//! The value is applied as a JSON merge patch (RFC 7396) to the original content,
+//! only paths present in the value are touched and `null` removes the key. The
+//! original content is returned untouched if the merge doesn't change anything.
+use serde_json::Value;
+
+use crate::{
+    codec::{merge_patch, Codec},
+    error::{Error, Result},
+};
+
+/// JSON codec
+#[derive(Debug, Default)]
+pub struct JsonCodec;
+
+impl Codec for JsonCodec {
+    fn decode(&self, content: &[u8]) -> Result<Value> {
+        serde_json::from_slice(content)
+            .map_err(|e| Error::with_message("unable to parse json").context("reason", e.to_string()))
+    }
+
+    fn encode(&self, value: &Value, original: Option<&[u8]>) -> Result<Vec<u8>> {
+        let original_value = match original {
+            Some(content) => Some(self.decode(content)?),
+            None => None,
+        };
+
+        let mut merged = original_value.clone().unwrap_or(Value::Null);
+        merge_patch(&mut merged, value);
+
+        if let (Some(content), Some(original_value)) = (original, original_value) {
+            if original_value == merged {
+                return Ok(content.to_vec());
+            }
+        }
+
+        let mut result = serde_json::to_vec_pretty(&merged)
+            .map_err(|e| Error::with_message("unable to serialize json").context("reason", e.to_string()))?;
+        result.push(b'\n');
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn encode_touches_owned_paths_only() {
+        let original = br#"{"hostname": "foo", "wifi": {"ssid": "bar", "psk": "baz"}}"#;
+        let value = json!({"wifi": {"psk": null, "ssid": "qux"}});
+
+        let encoded = JsonCodec.encode(&value, Some(original)).unwrap();
+        assert_eq!(
+            JsonCodec.decode(&encoded).unwrap(),
+            json!({"hostname": "foo", "wifi": {"ssid": "qux"}})
+        );
+    }
+
+    #[test]
+    fn encode_unchanged_value_preserves_content() {
+        let original = br#"{ "hostname":"foo" }"#;
+        let value = json!({"hostname": "foo"});
+        assert_eq!(JsonCodec.encode(&value, Some(original)).unwrap(), original.to_vec());
+    }
+}
@@ -0,0 +1,176 @@
+//! Target file codecs
+//!
+//! A codec converts target file content into a value, which can be validated
+//! against the schema, and the value back into the target file content.
+//!
+//! Encoding takes the original file content into account (if available) to
+//! preserve comments, ordering and anything the value doesn't cover.
+use serde_json::{Map, Value};
+
+use crate::{
+    error::{Error, Result},
+    schema::mapping::{RawTarget, TargetFormat},
+};
+
+pub use self::{
+    authorized_keys::AuthorizedKeysCodec,
+    config_txt::ConfigTxtCodec,
+    env_file::EnvFileCodec,
+    grub_env::GrubEnvCodec,
+    hosts::HostsCodec,
+    json::JsonCodec,
+    toml::TomlCodec,
+    uboot_env::{UBootEnvCodec, UBootEnvLayout},
+    yaml::YamlCodec,
+};
+
+mod authorized_keys;
+mod config_txt;
+mod env_file;
+mod grub_env;
+mod hosts;
+mod json;
+mod toml;
+mod uboot_env;
+mod yaml;
+
+/// Target file codec
+pub trait Codec {
+    /// Decodes file content into a value
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - A file content
+    fn decode(&self, content: &[u8]) -> Result<Value>;
+
+    /// Encodes value into file content
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - A value to encode
+    /// * `original` - An original file content (if exists) to preserve comments, ...
+    fn encode(&self, value: &Value, original: Option<&[u8]>) -> Result<Vec<u8>>;
+}
+
+/// Returns codec for the target
+///
+/// `None` is returned if there's no codec available for the target format.
+///
+/// # Arguments
+///
+/// * `target` - A target, the format & layout (`block`) are used
+pub fn codec(target: &RawTarget) -> Option<Box<dyn Codec>> {
+    match target.format() {
+        TargetFormat::Json => Some(Box::new(JsonCodec)),
+        TargetFormat::Yaml => Some(Box::new(YamlCodec)),
+        TargetFormat::Toml => Some(Box::new(TomlCodec)),
+        TargetFormat::ConfigTxt => Some(Box::new(ConfigTxtCodec)),
+        TargetFormat::UBootEnv => {
+            let layout = match target.block() {
+                Some(block) => UBootEnvLayout::Binary {
+                    size: block.size(),
+                    redundant: block.is_redundant(),
+                },
+                None => UBootEnvLayout::Text,
+            };
+            Some(Box::new(UBootEnvCodec::new(layout)))
+        }
+        TargetFormat::GrubEnv => Some(Box::new(GrubEnvCodec)),
+        TargetFormat::EnvFile => Some(Box::new(EnvFileCodec)),
+        TargetFormat::Hosts => Some(Box::new(HostsCodec)),
+        TargetFormat::AuthorizedKeys => Some(Box::new(AuthorizedKeysCodec)),
+        _ => None,
+    }
+}
+
+//
+// Shared helpers
+//
+
+pub(crate) fn content_as_str(content: &[u8]) -> Result<&str> {
+    std::str::from_utf8(content)
+        .map_err(|e| Error::with_message("content is not a valid utf-8").context("reason", e.to_string()))
+}
+
+/// Encodes scalar value as a string, booleans are encoded as `1` / `0`
+pub(crate) fn encode_multiline_scalar(key: &str, value: &Value) -> Result<String> {
+    match value {
+        Value::String(s) => Ok(s.clone()),
+        Value::Number(n) => Ok(n.to_string()),
+        Value::Bool(true) => Ok("1".to_string()),
+        Value::Bool(false) => Ok("0".to_string()),
+        _ => Err(Error::with_message("unable to encode non scalar value").context("key", key.to_string())),
+    }
+}
+
+/// Encodes scalar value as a single line string, booleans are encoded as `1` / `0`
+pub(crate) fn encode_scalar(key: &str, value: &Value) -> Result<String> {
+    let encoded = encode_multiline_scalar(key, value)?;
+
+    if encoded.contains('\n') {
+        return Err(Error::with_message("value must not contain new line").context("key", key.to_string()));
+    }
+
+    Ok(encoded)
+}
+
+/// Applies JSON merge patch (RFC 7396) to the target value
+///
+/// Only paths present in the patch are touched, `null` removes the key.
+pub(crate) fn merge_patch(target: &mut Value, patch: &Value) {
+    let patch = match patch {
+        Value::Object(patch) => patch,
+        _ => {
+            *target = patch.clone();
+            return;
+        }
+    };
+
+    if !target.is_object() {
+        *target = Value::Object(Map::new());
+    }
+    let target = target.as_object_mut().expect("target must be an object");
+
+    for (key, value) in patch {
+        if value.is_null() {
+            target.remove(key);
+        } else {
+            merge_patch(target.entry(key.to_string()).or_insert(Value::Null), value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn target(format: &str, block: &str) -> RawTarget {
+        let schema = format!(
+            "type: file\nformat: {}\nlocation:\n  partition: 0\n  path: /env\n{}",
+            format, block
+        );
+        serde_yaml::from_str(&schema).unwrap()
+    }
+
+    #[test]
+    fn uboot_env_layout() {
+        let value = json!({"bootdelay": "3"});
+
+        let text = codec(&target("uboot-env", "")).unwrap().encode(&value, None).unwrap();
+        assert_eq!(text, b"bootdelay=3\n");
+
+        let binary = codec(&target("uboot-env", "block:\n  size: 64\n  redundant: true\n"))
+            .unwrap()
+            .encode(&value, None)
+            .unwrap();
+        assert_eq!(binary.len(), 64);
+        assert_eq!(&binary[5..17], b"bootdelay=3\0");
+    }
+
+    #[test]
+    fn unsupported_format() {
+        assert!(codec(&target("ini", "")).is_none());
+    }
+}
@@ -0,0 +1,267 @@
+//! TOML codec
+//!
+//! Same merge semantics as the JSON codec, but the original document is edited
+//! in place, so comments & formatting of untouched parts are preserved. Datetime
+//! values are decoded as strings and they're encoded back as datetimes if the
+//! original value was a datetime. TOML doesn't support `null`, it's allowed only
+//! as an object value to remove the key.
+use serde_json::{Map, Number, Value};
+use toml_edit::{Array, ArrayOfTables, DocumentMut, InlineTable, Item, Table};
+
+use crate::{
+    codec::{content_as_str, Codec},
+    error::{Error, Result, ResultExt},
+};
+
+/// TOML codec
+#[derive(Debug, Default)]
+pub struct TomlCodec;
+
+fn parse(content: &[u8]) -> Result<DocumentMut> {
+    content_as_str(content)?
+        .parse()
+        .map_err(|e: toml_edit::TomlError| Error::with_message("unable to parse toml").context("reason", e.to_string()))
+}
+
+//
+// TOML -> JSON
+//
+
+fn decode_value(value: &toml_edit::Value) -> Result<Value> {
+    let result = match value {
+        toml_edit::Value::String(s) => Value::String(s.value().clone()),
+        toml_edit::Value::Integer(i) => Value::from(*i.value()),
+        toml_edit::Value::Float(f) => Number::from_f64(*f.value())
+            .map(Value::Number)
+            .ok_or_else(|| Error::with_message("unable to decode float").context("value", f.value().to_string()))?,
+        toml_edit::Value::Boolean(b) => Value::Bool(*b.value()),
+        toml_edit::Value::Datetime(d) => Value::String(d.value().to_string()),
+        toml_edit::Value::Array(a) => Value::Array(a.iter().map(decode_value).collect::<Result<_>>()?),
+        toml_edit::Value::InlineTable(t) => Value::Object(
+            t.iter()
+                .map(|(k, v)| decode_value(v).map(|v| (k.to_string(), v)))
+                .collect::<Result<_>>()?,
+        ),
+    };
+    Ok(result)
+}
+
+fn decode_table(table: &Table) -> Result<Value> {
+    Ok(Value::Object(
+        table
+            .iter()
+            .map(|(k, v)| decode_item(v).context("key", k.to_string()).map(|v| (k.to_string(), v)))
+            .collect::<Result<_>>()?,
+    ))
+}
+
+fn decode_item(item: &Item) -> Result<Value> {
+    match item {
+        Item::None => Ok(Value::Null),
+        Item::Value(v) => decode_value(v),
+        Item::Table(t) => decode_table(t),
+        Item::ArrayOfTables(a) => Ok(Value::Array(a.iter().map(decode_table).collect::<Result<_>>()?)),
+    }
+}
+
+//
+// JSON -> TOML
+//
+
+fn encode_value(value: &Value) -> Result<toml_edit::Value> {
+    let result = match value {
+        Value::Null => return Err(Error::with_message("null is not supported")),
+        Value::Bool(b) => (*b).into(),
+        Value::Number(n) => match (n.as_i64(), n.as_f64()) {
+            (Some(i), _) => i.into(),
+            (None, Some(f)) if !n.is_u64() => f.into(),
+            _ => return Err(Error::with_message("number out of range").context("value", n.to_string())),
+        },
+        Value::String(s) => s.as_str().into(),
+        Value::Array(items) => {
+            let mut array = Array::new();
+            for item in items {
+                array.push(encode_value(item)?);
+            }
+            array.into()
+        }
+        Value::Object(object) => {
+            let mut table = InlineTable::new();
+            for (k, v) in object.iter().filter(|(_, v)| !v.is_null()) {
+                table.insert(k.as_str(), encode_value(v).context("key", k.to_string())?);
+            }
+            table.into()
+        }
+    };
+    Ok(result)
+}
+
+fn encode_item(value: &Value) -> Result<Item> {
+    match value {
+        Value::Object(object) => {
+            let mut table = Table::new();
+            patch_table(&mut table, object)?;
+            Ok(Item::Table(table))
+        }
+        Value::Array(items) if !items.is_empty() && items.iter().all(Value::is_object) => {
+            let mut array = ArrayOfTables::new();
+            for item in items {
+                let mut table = Table::new();
+                patch_table(&mut table, item.as_object().expect("object"))?;
+                array.push(table);
+            }
+            Ok(Item::ArrayOfTables(array))
+        }
+        _ => Ok(Item::Value(encode_value(value)?)),
+    }
+}
+
+/// Replaces existing value, keeps decoration (comments, whitespace) and datetime type
+fn replace_value(existing: &mut toml_edit::Value, value: &Value) -> Result<()> {
+    let datetime = match (&existing, value) {
+        (toml_edit::Value::Datetime(_), Value::String(s)) => s.parse::<toml_edit::Datetime>().ok(),
+        _ => None,
+    };
+
+    let mut new = match datetime {
+        Some(datetime) => datetime.into(),
+        None => encode_value(value)?,
+    };
+    *new.decor_mut() = existing.decor().clone();
+    *existing = new;
+    Ok(())
+}
+
+fn patch_inline_table(table: &mut InlineTable, patch: &Map<String, Value>) -> Result<()> {
+    for (key, value) in patch {
+        if value.is_null() {
+            table.remove(key);
+            continue;
+        }
+
+        match table.get_mut(key) {
+            Some(toml_edit::Value::InlineTable(nested)) if value.is_object() => {
+                patch_inline_table(nested, value.as_object().expect("object"))
+            }
+            Some(existing) => replace_value(existing, value),
+            None => encode_value(value).map(|v| {
+                table.insert(key.as_str(), v);
+            }),
+        }
+        .context("key", key.to_string())?;
+    }
+    Ok(())
+}
+
+fn patch_table(table: &mut Table, patch: &Map<String, Value>) -> Result<()> {
+    for (key, value) in patch {
+        if value.is_null() {
+            table.remove(key);
+            continue;
+        }
+
+        match table.get_mut(key) {
+            Some(Item::Table(nested)) if value.is_object() => patch_table(nested, value.as_object().expect("object")),
+            Some(Item::Value(toml_edit::Value::InlineTable(nested))) if value.is_object() => {
+                patch_inline_table(nested, value.as_object().expect("object"))
+            }
+            Some(Item::Value(existing)) => replace_value(existing, value),
+            _ => encode_item(value).map(|item| {
+                table.insert(key, item);
+            }),
+        }
+        .context("key", key.to_string())?;
+    }
+    Ok(())
+}
+
+impl Codec for TomlCodec {
+    fn decode(&self, content: &[u8]) -> Result<Value> {
+        decode_table(parse(content)?.as_table())
+    }
+
+    fn encode(&self, value: &Value, original: Option<&[u8]>) -> Result<Vec<u8>> {
+        let patch = value
+            .as_object()
+            .ok_or_else(|| Error::with_message("unable to encode non object value"))?;
+
+        let mut document = match original {
+            Some(content) => parse(content)?,
+            None => DocumentMut::new(),
+        };
+
+        patch_table(document.as_table_mut(), patch)?;
+
+        Ok(document.to_string().into_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const TOML: &str = r#"# Application config
+name = "foo" # application name
+created = 1979-05-27T07:32:00Z
+
+[database]
+# Connection
+host = "localhost"
+port = 5432
+"#;
+
+    fn encode(value: &Value) -> String {
+        String::from_utf8(TomlCodec.encode(value, Some(TOML.as_bytes())).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn decode() {
+        assert_eq!(
+            TomlCodec.decode(TOML.as_bytes()).unwrap(),
+            json!({
+                "name": "foo",
+                "created": "1979-05-27T07:32:00Z",
+                "database": {"host": "localhost", "port": 5432}
+            })
+        );
+    }
+
+    #[test]
+    fn encode_decoded_value_preserves_content() {
+        let value = TomlCodec.decode(TOML.as_bytes()).unwrap();
+        assert_eq!(encode(&value), TOML);
+    }
+
+    #[test]
+    fn encode_preserves_comments() {
+        let value = json!({"name": "bar", "database": {"port": 5433, "host": null}});
+        let expected = TOML
+            .replace("\"foo\" #", "\"bar\" #")
+            // Comment belongs to the removed key
+            .replace("# Connection\nhost = \"localhost\"\n", "")
+            .replace("5432", "5433");
+        assert_eq!(encode(&value), expected);
+    }
+
+    #[test]
+    fn encode_new_tables() {
+        let value = json!({"servers": [{"name": "alpha"}, {"name": "beta"}], "cache": {"size": 10}});
+        let encoded = encode(&value);
+        assert!(encoded.starts_with(TOML));
+        assert_eq!(
+            TomlCodec.decode(encoded.as_bytes()).unwrap()["servers"],
+            json!([{"name": "alpha"}, {"name": "beta"}])
+        );
+        assert_eq!(
+            TomlCodec.decode(encoded.as_bytes()).unwrap()["cache"],
+            json!({"size": 10})
+        );
+    }
+
+    #[test]
+    fn fail_on_null_in_array() {
+        assert!(TomlCodec.encode(&json!({"list": [1, null]}), None).is_err());
+    }
+}
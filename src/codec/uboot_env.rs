@@ -0,0 +1,369 @@
+//! U-Boot environment codec
+//!
+//! Supports both environment variants:
+//!
+//! * text (`uEnv.txt`) - `key=value` lines, imported with `env import -t`
+//! * binary - CRC32 (little endian) of the data, optional flags byte (redundant
+//!   environment) and `key=value` entries separated with `\0`, terminated with `\0\0`
+//!   and padded with `\0` up to the environment size
+//!
+//! The value must be a flat object, all values are decoded as strings. Binary content
+//! is detected automatically when decoding, checksum is validated and the redundant
+//! environment is detected from the checksum position. When an original content is
+//! provided, encoding preserves its variant, size and flags byte. Otherwise the codec
+//! layout is used, `codec()` builds it from the target `block` (`size` & `redundant`).
+use std::collections::HashSet;
+
+use serde_json::{Map, Value};
+
+use crate::{
+    codec::{content_as_str, encode_scalar, Codec},
+    error::{Error, Result, ResultExt},
+};
+
+const CRC_SIZE: usize = 4;
+const FLAGS_SIZE: usize = 1;
+// Active flag value for new redundant environments, valid for both, boolean
+// and incremental, flag schemes
+const ACTIVE_FLAGS: u8 = 1;
+
+/// U-Boot environment layout
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum UBootEnvLayout {
+    /// Plain text (`uEnv.txt`)
+    Text,
+    /// Binary environment block
+    Binary {
+        /// Whole environment size including header (`CONFIG_ENV_SIZE`)
+        size: usize,
+        /// Redundant environment (header contains flags byte)
+        redundant: bool,
+    },
+}
+
+/// U-Boot environment codec
+#[derive(Debug)]
+pub struct UBootEnvCodec {
+    layout: UBootEnvLayout,
+}
+
+impl UBootEnvCodec {
+    /// Creates new codec
+    ///
+    /// # Arguments
+    ///
+    /// * `layout` - A layout used for encoding if there's no original content
+    pub fn new(layout: UBootEnvLayout) -> UBootEnvCodec {
+        UBootEnvCodec { layout }
+    }
+
+    pub fn layout(&self) -> UBootEnvLayout {
+        self.layout
+    }
+}
+
+impl Default for UBootEnvCodec {
+    fn default() -> UBootEnvCodec {
+        UBootEnvCodec::new(UBootEnvLayout::Text)
+    }
+}
+
+/// Binary environment header
+struct Header {
+    size: usize,
+    flags: Option<u8>,
+}
+
+impl Header {
+    fn data_offset(&self) -> usize {
+        match self.flags {
+            Some(_) => CRC_SIZE + FLAGS_SIZE,
+            None => CRC_SIZE,
+        }
+    }
+}
+
+fn is_binary(content: &[u8]) -> bool {
+    content.contains(&0)
+}
+
+fn decode_binary_header(content: &[u8]) -> Result<Header> {
+    if content.len() <= CRC_SIZE + FLAGS_SIZE {
+        return Err(Error::with_message("binary environment is too small").context("size", content.len().to_string()));
+    }
+
+    let mut stored = [0; CRC_SIZE];
+    stored.copy_from_slice(&content[..CRC_SIZE]);
+    let stored = u32::from_le_bytes(stored);
+
+    if crc32fast::hash(&content[CRC_SIZE..]) == stored {
+        return Ok(Header {
+            size: content.len(),
+            flags: None,
+        });
+    }
+
+    if crc32fast::hash(&content[CRC_SIZE + FLAGS_SIZE..]) == stored {
+        return Ok(Header {
+            size: content.len(),
+            flags: Some(content[CRC_SIZE]),
+        });
+    }
+
+    Err(Error::with_message("binary environment checksum mismatch").context("crc", format!("{:#010x}", stored)))
+}
+
+fn decode_binary(content: &[u8]) -> Result<Map<String, Value>> {
+    let header = decode_binary_header(content)?;
+
+    let mut result = Map::new();
+
+    for entry in content[header.data_offset()..].split(|x| *x == 0) {
+        // Empty entry means end of the environment (\0\0)
+        if entry.is_empty() {
+            break;
+        }
+
+        let entry = content_as_str(entry)?;
+        let idx = entry
+            .find('=')
+            .ok_or_else(|| Error::with_message("invalid environment entry").context("entry", entry.to_string()))?;
+        result.insert(entry[..idx].to_string(), Value::String(entry[idx + 1..].to_string()));
+    }
+
+    Ok(result)
+}
+
+fn encode_binary(entries: &[(String, String)], size: usize, flags: Option<u8>) -> Result<Vec<u8>> {
+    let header = Header { size, flags };
+    let offset = header.data_offset();
+
+    let mut data = vec![];
+    for (key, value) in entries {
+        data.extend_from_slice(key.as_bytes());
+        data.push(b'=');
+        data.extend_from_slice(value.as_bytes());
+        data.push(0);
+    }
+    // Environment must be terminated with \0\0
+    if data.is_empty() {
+        data.push(0);
+    }
+    data.push(0);
+
+    if offset + data.len() > size {
+        return Err(Error::with_message("environment doesn't fit into the binary block")
+            .context("size", size.to_string())
+            .context("required", (offset + data.len()).to_string()));
+    }
+    data.resize(size - offset, 0);
+
+    let mut result = Vec::with_capacity(size);
+    result.extend_from_slice(&crc32fast::hash(&data).to_le_bytes());
+    if let Some(flags) = flags {
+        result.push(flags);
+    }
+    result.extend(data);
+    Ok(result)
+}
+
+/// Returns key & value for the `uEnv.txt` line, `None` for empty lines and comments
+fn parse_text_line(line: &str) -> Option<(&str, &str)> {
+    let line = line.trim_end_matches('\r');
+
+    if line.trim().is_empty() || line.trim_start().starts_with('#') {
+        return None;
+    }
+
+    line.find('=').map(|idx| (&line[..idx], &line[idx + 1..]))
+}
+
+fn decode_text(content: &[u8]) -> Result<Map<String, Value>> {
+    let content = content_as_str(content)?;
+
+    let mut result = Map::new();
+    for (key, value) in content.lines().filter_map(parse_text_line) {
+        result.insert(key.to_string(), Value::String(value.to_string()));
+    }
+    Ok(result)
+}
+
+fn encode_text(entries: &[(String, String)], original: &str) -> Vec<u8> {
+    let mut written = HashSet::new();
+    let mut lines = vec![];
+
+    for line in original.lines() {
+        match parse_text_line(line) {
+            Some((key, _)) => {
+                // Replace the value (first occurrence only) or drop the line
+                if let Some((key, value)) = entries.iter().find(|(k, _)| k == key) {
+                    if written.insert(key.as_str()) {
+                        lines.push(format!("{}={}", key, value));
+                    }
+                }
+            }
+            None => lines.push(line.to_string()),
+        };
+    }
+
+    lines.extend(
+        entries
+            .iter()
+            .filter(|(k, _)| !written.contains(k.as_str()))
+            .map(|(k, v)| format!("{}={}", k, v)),
+    );
+
+    let mut result = lines.join("\n");
+    if !result.is_empty() {
+        result.push('\n');
+    }
+    result.into_bytes()
+}
+
+fn encode_entries(value: &Value) -> Result<Vec<(String, String)>> {
+    let object = value
+        .as_object()
+        .ok_or_else(|| Error::with_message("unable to encode non object value"))?;
+
+    let mut entries = vec![];
+    for (key, value) in object {
+        if key.is_empty() || key.contains(&['=', '\0', '\n'][..]) {
+            return Err(Error::with_message("invalid variable name").context("key", key.to_string()));
+        }
+
+        if value.is_null() {
+            continue;
+        }
+
+        let value = encode_scalar(key, value)?;
+        if value.contains('\0') {
+            return Err(Error::with_message("value must not contain \\0").context("key", key.to_string()));
+        }
+
+        entries.push((key.to_string(), value));
+    }
+    Ok(entries)
+}
+
+impl Codec for UBootEnvCodec {
+    fn decode(&self, content: &[u8]) -> Result<Value> {
+        let result = if is_binary(content) {
+            decode_binary(content).frame_with_name("binary environment")?
+        } else {
+            decode_text(content).frame_with_name("text environment")?
+        };
+        Ok(Value::Object(result))
+    }
+
+    fn encode(&self, value: &Value, original: Option<&[u8]>) -> Result<Vec<u8>> {
+        let entries = encode_entries(value)?;
+
+        match original {
+            Some(content) if is_binary(content) => {
+                let header = decode_binary_header(content)?;
+                encode_binary(&entries, header.size, header.flags)
+            }
+            Some(content) => Ok(encode_text(&entries, content_as_str(content)?)),
+            None => match self.layout {
+                UBootEnvLayout::Text => Ok(encode_text(&entries, "")),
+                UBootEnvLayout::Binary { size, redundant } => {
+                    encode_binary(&entries, size, if redundant { Some(ACTIVE_FLAGS) } else { None })
+                }
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const UENV_TXT: &str = r#"# Boot arguments
+bootargs=console=ttyS0,115200 root=/dev/mmcblk0p2
+fdtfile=bcm2711-rpi-4-b.dtb
+"#;
+
+    fn binary_codec(redundant: bool) -> UBootEnvCodec {
+        UBootEnvCodec::new(UBootEnvLayout::Binary { size: 64, redundant })
+    }
+
+    #[test]
+    fn decode_text() {
+        let value = UBootEnvCodec::default().decode(UENV_TXT.as_bytes()).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "bootargs": "console=ttyS0,115200 root=/dev/mmcblk0p2",
+                "fdtfile": "bcm2711-rpi-4-b.dtb"
+            })
+        );
+    }
+
+    #[test]
+    fn encode_text_preserves_comments() {
+        let codec = UBootEnvCodec::default();
+        let mut value = codec.decode(UENV_TXT.as_bytes()).unwrap();
+        value["fdtfile"] = Value::Null;
+        value["bootdelay"] = json!(3);
+
+        let encoded = codec.encode(&value, Some(UENV_TXT.as_bytes())).unwrap();
+        assert_eq!(
+            String::from_utf8(encoded).unwrap(),
+            "# Boot arguments\nbootargs=console=ttyS0,115200 root=/dev/mmcblk0p2\nbootdelay=3\n"
+        );
+    }
+
+    #[test]
+    fn binary_roundtrip() {
+        let value = json!({"bootdelay": "3", "bootcmd": "run mmcboot"});
+
+        for redundant in &[false, true] {
+            let codec = binary_codec(*redundant);
+            let encoded = codec.encode(&value, None).unwrap();
+            assert_eq!(encoded.len(), 64);
+            assert_eq!(codec.decode(&encoded).unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn binary_layout() {
+        let encoded = binary_codec(true).encode(&json!({"a": "1"}), None).unwrap();
+        assert_eq!(encoded[CRC_SIZE], ACTIVE_FLAGS);
+        assert_eq!(&encoded[CRC_SIZE + FLAGS_SIZE..CRC_SIZE + FLAGS_SIZE + 5], b"a=1\0\0");
+        assert_eq!(
+            &encoded[..CRC_SIZE],
+            &crc32fast::hash(&encoded[CRC_SIZE + FLAGS_SIZE..]).to_le_bytes()
+        );
+    }
+
+    #[test]
+    fn encode_binary_preserves_original_layout() {
+        let mut original = binary_codec(true).encode(&json!({"a": "1"}), None).unwrap();
+        // Obsolete flag must be preserved
+        original[CRC_SIZE] = 0;
+        let crc = crc32fast::hash(&original[CRC_SIZE + FLAGS_SIZE..]).to_le_bytes();
+        original[..CRC_SIZE].copy_from_slice(&crc);
+
+        let encoded = UBootEnvCodec::default()
+            .encode(&json!({"b": "2"}), Some(&original))
+            .unwrap();
+        assert_eq!(encoded.len(), 64);
+        assert_eq!(encoded[CRC_SIZE], 0);
+        assert_eq!(UBootEnvCodec::default().decode(&encoded).unwrap(), json!({"b": "2"}));
+    }
+
+    #[test]
+    fn fail_on_checksum_mismatch() {
+        let mut encoded = binary_codec(false).encode(&json!({"a": "1"}), None).unwrap();
+        encoded[CRC_SIZE] = b'b';
+        assert!(UBootEnvCodec::default().decode(&encoded).is_err());
+    }
+
+    #[test]
+    fn fail_on_overflow() {
+        let value = json!({ "bootargs": "x".repeat(64) });
+        assert!(binary_codec(false).encode(&value, None).is_err());
+    }
+}
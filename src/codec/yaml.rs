@@ -0,0 +1,433 @@
+//! YAML codec
+//!
+//! Same merge semantics as the JSON codec. If the merge changes only existing scalar
+//! values of block mappings (`key: value` lines) or adds / removes keys of block mappings,
+//! these lines are edited in place and comments & formatting of the whole document are
+//! preserved. New keys are appended to the parent mapping.
+//!
+//! Limitation: the document is serialized again if a value inside a sequence or a flow
+//! collection (`[...]`, `{...}`) is changed, the scalar is a multi line one, the type of
+//! the value is changed or the edited document doesn't decode to the merged value. All
+//! comments are lost and the formatting is normalized in this case.
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde_json::Value;
+
+use crate::{
+    codec::{content_as_str, merge_patch, Codec},
+    error::{Error, Result},
+};
+
+lazy_static! {
+    // `<indent><key>:<value>`, key can be quoted
+    static ref KEY_LINE_REGEX: Regex =
+        Regex::new(r#"^( *)("[^"]*"|'[^']*'|[^\s#'"\-?:,\[\]{}][^#:]*?) *:(?: +|$)(.*)$"#).unwrap();
+}
+
+/// YAML codec
+#[derive(Debug, Default)]
+pub struct YamlCodec;
+
+/// Change of the original document made by the merge
+#[derive(Debug)]
+enum Change {
+    /// Scalar value has changed
+    Set(Vec<String>, Value),
+    /// Key was added
+    Add(Vec<String>, Value),
+    /// Key was removed
+    Remove(Vec<String>),
+}
+
+/// Collects changes made by the merge, `false` if anything else has changed
+fn collect_changes(original: &Value, merged: &Value, path: &mut Vec<String>, result: &mut Vec<Change>) -> bool {
+    match (original, merged) {
+        (Value::Object(original), Value::Object(merged)) => {
+            for key in original.keys().filter(|k| !merged.contains_key(*k)) {
+                path.push(key.to_string());
+                result.push(Change::Remove(path.clone()));
+                path.pop();
+            }
+
+            for (key, value) in merged.iter().filter(|(k, _)| !original.contains_key(*k)) {
+                path.push(key.to_string());
+                result.push(Change::Add(path.clone(), value.clone()));
+                path.pop();
+            }
+
+            original
+                .iter()
+                .filter(|(k, _)| merged.contains_key(*k))
+                .all(|(key, value)| {
+                    path.push(key.to_string());
+                    let result = collect_changes(value, &merged[key], path, result);
+                    path.pop();
+                    result
+                })
+        }
+        (original, merged) if original == merged => true,
+        (Value::Array(_), _) | (Value::Object(_), _) | (_, Value::Array(_)) | (_, Value::Object(_)) => false,
+        (_, merged) => {
+            result.push(Change::Set(path.clone(), merged.clone()));
+            true
+        }
+    }
+}
+
+fn unquote(key: &str) -> &str {
+    if key.len() >= 2 && (key.starts_with('"') && key.ends_with('"') || key.starts_with('\'') && key.ends_with('\'')) {
+        &key[1..key.len() - 1]
+    } else {
+        key
+    }
+}
+
+/// Returns the length of the scalar at the beginning of `s`, trailing comment is excluded
+fn scalar_len(s: &str) -> Option<usize> {
+    let len = match s.chars().next()? {
+        '"' => s[1..].find('"').filter(|idx| !s[1..=*idx].ends_with('\\'))? + 2,
+        '\'' => s[1..].find('\'')? + 2,
+        // Block scalars, flow collections, anchors, aliases & tags
+        '|' | '>' | '[' | '{' | '&' | '*' | '!' | '#' => return None,
+        _ => s.find(" #").unwrap_or(s.len()),
+    };
+    Some(s[..len].trim_end().len())
+}
+
+fn split_indent(line: &str) -> (usize, &str) {
+    let trimmed = line.trim_start_matches(' ');
+    (line.len() - trimmed.len(), trimmed)
+}
+
+fn is_blank_or_comment(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed.is_empty() || trimmed.starts_with('#')
+}
+
+/// Finds the `key: value` line of the path, returns the line index, indentation & value start
+fn find_key(lines: &[&str], path: &[String]) -> Option<(usize, usize, usize)> {
+    // (indent, key), `None` key for sequence items
+    let mut parents: Vec<(usize, Option<String>)> = vec![];
+
+    for (idx, line) in lines.iter().enumerate() {
+        let (indent, trimmed) = split_indent(line);
+
+        if trimmed.is_empty() || trimmed.starts_with('#') || (idx == 0 && *line == "---") {
+            continue;
+        }
+
+        // Tabs, multiple documents, ...
+        if trimmed.starts_with('\t') || trimmed.starts_with("---") || trimmed.starts_with("...") {
+            return None;
+        }
+
+        while parents.last().map(|(x, _)| *x >= indent).unwrap_or(false) {
+            parents.pop();
+        }
+
+        if trimmed.starts_with('-') {
+            parents.push((indent, None));
+            continue;
+        }
+
+        let captures = match KEY_LINE_REGEX.captures(line) {
+            Some(x) => x,
+            None => continue,
+        };
+        let key = unquote(&captures[2]).to_string();
+        let value = captures.get(3).expect("invalid regex");
+
+        let matches = parents.len() + 1 == path.len()
+            && parents
+                .iter()
+                .zip(path)
+                .all(|((_, k), p)| k.as_deref() == Some(p.as_str()))
+            && key == path[path.len() - 1];
+
+        if matches {
+            return Some((idx, indent, value.start()));
+        }
+
+        if value.as_str().is_empty() || value.as_str().starts_with('#') {
+            parents.push((indent, Some(key)));
+        }
+    }
+
+    None
+}
+
+/// Returns the end (exclusive) of the value block of the `key: value` line, trailing
+/// blank lines & comments are excluded
+fn block_end(lines: &[&str], idx: usize, indent: usize) -> usize {
+    let mut end = idx + 1;
+
+    for (i, line) in lines.iter().enumerate().skip(idx + 1) {
+        if is_blank_or_comment(line) {
+            continue;
+        }
+
+        let (line_indent, trimmed) = split_indent(line);
+        // Block sequences can be indented at the same level as the key
+        if line_indent < indent || (line_indent == indent && !trimmed.starts_with('-')) {
+            break;
+        }
+        end = i + 1;
+    }
+
+    end
+}
+
+fn encode_scalar(value: &Value) -> Option<String> {
+    let encoded = serde_yaml::to_string(value).ok()?;
+    let encoded = encoded.strip_prefix("---").unwrap_or(&encoded).trim();
+
+    if encoded.contains('\n') {
+        return None;
+    }
+
+    Some(encoded.to_string())
+}
+
+/// Encodes the `key: value` block lines with the given indentation
+fn encode_entry(key: &str, value: &Value, indent: usize) -> Option<Vec<String>> {
+    let mut entry = serde_json::Map::new();
+    entry.insert(key.to_string(), value.clone());
+
+    let encoded = serde_yaml::to_string(&entry).ok()?;
+    let encoded = encoded.strip_prefix("---\n").unwrap_or(&encoded).trim_end();
+
+    Some(
+        encoded
+            .split('\n')
+            .map(|line| format!("{:indent$}{}", "", line, indent = indent))
+            .collect(),
+    )
+}
+
+/// Applies the change to the lines, `None` if it's not possible
+fn apply_change(lines: &mut Vec<String>, change: Change) -> Option<()> {
+    let view: Vec<&str> = lines.iter().map(String::as_str).collect();
+
+    match change {
+        Change::Set(path, value) => {
+            let (idx, _, start) = find_key(&view, &path)?;
+            let end = start + scalar_len(&view[idx][start..])?;
+            let encoded = encode_scalar(&value)?;
+            lines[idx].replace_range(start..end, &encoded);
+        }
+        Change::Remove(path) => {
+            let (idx, indent, _) = find_key(&view, &path)?;
+            let end = block_end(&view, idx, indent);
+            lines.drain(idx..end);
+        }
+        Change::Add(path, value) => {
+            let (key, parent) = path.split_last()?;
+
+            // New keys are appended to the parent mapping with the indentation of its first key
+            let (at, indent) = if parent.is_empty() {
+                let at = view
+                    .iter()
+                    .rposition(|x| !x.trim().is_empty())
+                    .map(|x| x + 1)
+                    .unwrap_or(0);
+                (at, 0)
+            } else {
+                let (idx, indent, start) = find_key(&view, parent)?;
+                if !is_blank_or_comment(&view[idx][start..]) {
+                    return None;
+                }
+                let end = block_end(&view, idx, indent);
+                let first = view[idx + 1..end].iter().find(|x| !is_blank_or_comment(x))?;
+                (end, split_indent(first).0)
+            };
+
+            let encoded = encode_entry(key, &value, indent)?;
+            lines.splice(at..at, encoded);
+        }
+    }
+
+    Some(())
+}
+
+/// Edits the changed lines in place, `None` if it's not possible
+fn edit_in_place(content: &str, original: &Value, merged: &Value) -> Option<String> {
+    let mut changes = vec![];
+    if !collect_changes(original, merged, &mut vec![], &mut changes) {
+        return None;
+    }
+
+    let mut lines: Vec<String> = content.split('\n').map(str::to_string).collect();
+
+    for change in changes {
+        apply_change(&mut lines, change)?;
+    }
+
+    let result = lines.join("\n");
+
+    // Anchors, aliases & anything unexpected
+    match serde_yaml::from_str::<Value>(&result) {
+        Ok(x) if x == *merged => Some(result),
+        _ => None,
+    }
+}
+
+impl Codec for YamlCodec {
+    fn decode(&self, content: &[u8]) -> Result<Value> {
+        serde_yaml::from_slice(content)
+            .map_err(|e| Error::with_message("unable to parse yaml").context("reason", e.to_string()))
+    }
+
+    fn encode(&self, value: &Value, original: Option<&[u8]>) -> Result<Vec<u8>> {
+        let original_value = match original {
+            Some(content) => Some(self.decode(content)?),
+            None => None,
+        };
+
+        let mut merged = original_value.clone().unwrap_or(Value::Null);
+        merge_patch(&mut merged, value);
+
+        if let (Some(content), Some(original_value)) = (original, original_value) {
+            if original_value == merged {
+                return Ok(content.to_vec());
+            }
+
+            if let Some(edited) = edit_in_place(content_as_str(content)?, &original_value, &merged) {
+                return Ok(edited.into_bytes());
+            }
+        }
+
+        serde_yaml::to_vec(&merged)
+            .map_err(|e| Error::with_message("unable to serialize yaml").context("reason", e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const YAML: &str = r#"# Application config
+name: foo
+database:
+  # Primary database
+  host: localhost
+  port: 5432 # default port
+  "user name": 'bar'
+"#;
+
+    #[test]
+    fn decode() {
+        assert_eq!(
+            YamlCodec.decode(YAML.as_bytes()).unwrap(),
+            json!({"name": "foo", "database": {"host": "localhost", "port": 5432, "user name": "bar"}})
+        );
+    }
+
+    #[test]
+    fn encode_touches_owned_paths_only() {
+        let value = json!({"database": {"port": 5433, "password": "baz"}});
+
+        let encoded = YamlCodec.encode(&value, Some(YAML.as_bytes())).unwrap();
+        assert_eq!(
+            YamlCodec.decode(&encoded).unwrap(),
+            json!({
+                "name": "foo",
+                "database": {"host": "localhost", "port": 5433, "user name": "bar", "password": "baz"}
+            })
+        );
+    }
+
+    #[test]
+    fn encode_changed_scalars_preserves_comments() {
+        let value = json!({"name": "foo: bar", "database": {"port": 5433, "user name": "baz"}});
+
+        let encoded = YamlCodec.encode(&value, Some(YAML.as_bytes())).unwrap();
+        assert_eq!(
+            String::from_utf8(encoded).unwrap(),
+            r#"# Application config
+name: "foo: bar"
+database:
+  # Primary database
+  host: localhost
+  port: 5433 # default port
+  "user name": baz
+"#
+        );
+    }
+
+    #[test]
+    fn encode_new_key_preserves_comments() {
+        let value = json!({"database": {"password": "baz", "options": {"ssl": true}}, "debug": false});
+
+        let encoded = YamlCodec.encode(&value, Some(YAML.as_bytes())).unwrap();
+        assert_eq!(
+            String::from_utf8(encoded).unwrap(),
+            r#"# Application config
+name: foo
+database:
+  # Primary database
+  host: localhost
+  port: 5432 # default port
+  "user name": 'bar'
+  options:
+    ssl: true
+  password: baz
+debug: false
+"#
+        );
+    }
+
+    #[test]
+    fn encode_removed_key_preserves_comments() {
+        let yaml = r#"# Application config
+name: foo
+database:
+  # Primary database
+  host: localhost
+  replicas:
+  - a
+  - b
+  port: 5432 # default port
+"#;
+        let value = json!({"database": {"host": null, "replicas": null}});
+
+        let encoded = YamlCodec.encode(&value, Some(yaml.as_bytes())).unwrap();
+        assert_eq!(
+            String::from_utf8(encoded).unwrap(),
+            r#"# Application config
+name: foo
+database:
+  # Primary database
+  port: 5432 # default port
+"#
+        );
+    }
+
+    #[test]
+    fn encode_sequence_scalar() {
+        let yaml = "# Servers\nservers:\n  - name: a\n    port: 1\n";
+        let value = json!({"servers": [{"name": "a", "port": 2}]});
+
+        let encoded = YamlCodec.encode(&value, Some(yaml.as_bytes())).unwrap();
+        assert_eq!(YamlCodec.decode(&encoded).unwrap(), value);
+    }
+
+    #[test]
+    fn encode_aliased_scalar() {
+        let yaml = "# Defaults\nbase: &port 1\nother: *port\n";
+        let value = json!({"base": 2});
+
+        let encoded = YamlCodec.encode(&value, Some(yaml.as_bytes())).unwrap();
+        assert_eq!(YamlCodec.decode(&encoded).unwrap(), json!({"base": 2, "other": 1}));
+    }
+
+    #[test]
+    fn encode_unchanged_value_preserves_comments() {
+        let value = json!({"database": {"port": 5432}});
+        assert_eq!(
+            YamlCodec.encode(&value, Some(YAML.as_bytes())).unwrap(),
+            YAML.as_bytes().to_vec()
+        );
+    }
+}
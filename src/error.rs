@@ -160,6 +160,11 @@ impl Error {
         Error { inner: Box::new(inner) }
     }
 
+    /// Returns error message
+    pub fn message(&self) -> &str {
+        &self.inner.message
+    }
+
     /// Appends key, value pair to context of the last frame
     ///
     /// # Arguments
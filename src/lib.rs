@@ -1,4 +1,7 @@
+pub mod codec;
 pub mod error;
+pub mod parser;
 pub mod schema;
+pub mod tables;
 mod utils;
 pub mod validator;
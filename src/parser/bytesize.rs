@@ -0,0 +1,202 @@
+//! Size in bytes
+//!
+//! ```text
+//! 512MiB, 1.5GB, 4K, 1024
+//! ```
+//!
+//! A number followed by an optional unit. Units are case insensitive, SI units (`kB`, `MB`, ...)
+//! are powers of 1000, IEC units (`KiB`, `MiB`, ...) and single letter units (`K`, `M`, ...)
+//! are powers of 1024. The result must be a whole number of bytes.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use reconfix::parser::bytesize::ByteSize;
+//!
+//! let size: ByteSize = "1.5KiB".parse().unwrap();
+//! assert_eq!(size.as_u64(), 1536);
+//! assert_eq!("512MiB".parse::<ByteSize>().unwrap().to_string(), "512MiB");
+//! ```
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::{Error, Result};
+
+const KB: u64 = 1000;
+const KIB: u64 = 1024;
+
+const UNITS: &[(&str, u64)] = &[
+    ("b", 1),
+    ("kb", KB),
+    ("mb", KB * KB),
+    ("gb", KB * KB * KB),
+    ("tb", KB * KB * KB * KB),
+    ("pb", KB * KB * KB * KB * KB),
+    ("k", KIB),
+    ("m", KIB * KIB),
+    ("g", KIB * KIB * KIB),
+    ("t", KIB * KIB * KIB * KIB),
+    ("p", KIB * KIB * KIB * KIB * KIB),
+    ("kib", KIB),
+    ("mib", KIB * KIB),
+    ("gib", KIB * KIB * KIB),
+    ("tib", KIB * KIB * KIB * KIB),
+    ("pib", KIB * KIB * KIB * KIB * KIB),
+];
+
+/// Units used by `Display`, the largest one which divides the size is used
+const DISPLAY_UNITS: &[(&str, u64)] = &[
+    ("PiB", KIB * KIB * KIB * KIB * KIB),
+    ("TiB", KIB * KIB * KIB * KIB),
+    ("GiB", KIB * KIB * KIB),
+    ("MiB", KIB * KIB),
+    ("KiB", KIB),
+    ("PB", KB * KB * KB * KB * KB),
+    ("TB", KB * KB * KB * KB),
+    ("GB", KB * KB * KB),
+    ("MB", KB * KB),
+    ("kB", KB),
+];
+
+/// Number of bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ByteSize(u64);
+
+impl ByteSize {
+    pub fn as_u64(&self) -> u64 {
+        self.0
+    }
+}
+
+impl From<u64> for ByteSize {
+    fn from(bytes: u64) -> ByteSize {
+        ByteSize(bytes)
+    }
+}
+
+impl FromStr for ByteSize {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<ByteSize> {
+        let idx = s.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(s.len());
+        let (number, unit) = (&s[..idx], &s[idx..]);
+
+        let multiplier = match unit.to_lowercase().as_str() {
+            "" => 1,
+            unit => UNITS
+                .iter()
+                .find(|(name, _)| *name == unit)
+                .map(|(_, multiplier)| *multiplier)
+                .ok_or_else(|| Error::with_message(format!("unknown unit '{}'", &s[idx..])))?,
+        };
+
+        let invalid = || Error::with_message("invalid number").context("number", number.to_string());
+
+        let (integer, fraction) = match number.find('.') {
+            Some(idx) if idx + 1 < number.len() => (&number[..idx], &number[idx + 1..]),
+            Some(_) => return Err(invalid()),
+            None => (number, ""),
+        };
+
+        if integer.is_empty() || !fraction.chars().all(|c| c.is_ascii_digit()) {
+            return Err(invalid());
+        }
+
+        let too_large = || Error::with_message("size is too large");
+
+        // Compute in u128, fraction can't have more digits than u64 anyway
+        let fraction_scale = 10u128.checked_pow(fraction.len() as u32).ok_or_else(invalid)?;
+        let integer: u128 = integer.parse().map_err(|_| too_large())?;
+        let fraction: u128 = if fraction.is_empty() {
+            0
+        } else {
+            fraction.parse().map_err(|_| invalid())?
+        };
+
+        let fraction_bytes = fraction * u128::from(multiplier);
+        if !fraction_bytes.is_multiple_of(fraction_scale) {
+            return Err(Error::with_message("size must be a whole number of bytes").context("size", s.to_string()));
+        }
+
+        let bytes = integer
+            .checked_mul(u128::from(multiplier))
+            .and_then(|x| x.checked_add(fraction_bytes / fraction_scale))
+            .ok_or_else(too_large)?;
+
+        let bytes = u64::try_from(bytes).map_err(|_| too_large())?;
+        Ok(ByteSize(bytes))
+    }
+}
+
+impl fmt::Display for ByteSize {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match DISPLAY_UNITS
+            .iter()
+            .find(|(_, unit)| self.0 != 0 && self.0.is_multiple_of(*unit))
+        {
+            Some((name, unit)) => write!(f, "{}{}", self.0 / unit, name),
+            None => write!(f, "{}B", self.0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bytes(s: &str) -> u64 {
+        s.parse::<ByteSize>().unwrap().as_u64()
+    }
+
+    #[test]
+    fn units() {
+        assert_eq!(bytes("1024"), 1024);
+        assert_eq!(bytes("0"), 0);
+        assert_eq!(bytes("10B"), 10);
+        assert_eq!(bytes("1kB"), 1000);
+        assert_eq!(bytes("1KB"), 1000);
+        assert_eq!(bytes("1K"), 1024);
+        assert_eq!(bytes("1KiB"), 1024);
+        assert_eq!(bytes("512MiB"), 512 * 1024 * 1024);
+        assert_eq!(bytes("512mib"), 512 * 1024 * 1024);
+        assert_eq!(bytes("1.5GB"), 1_500_000_000);
+        assert_eq!(bytes("1.5KiB"), 1536);
+        assert_eq!(bytes("16PiB"), 16 * KIB.pow(5));
+    }
+
+    #[test]
+    fn display() {
+        for (s, expected) in &[
+            ("512MiB", "512MiB"),
+            ("1024K", "1MiB"),
+            ("1.5GB", "1500MB"),
+            ("1000", "1kB"),
+            ("1023", "1023B"),
+            ("0", "0B"),
+        ] {
+            assert_eq!(&s.parse::<ByteSize>().unwrap().to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn invalid() {
+        for s in &[
+            "",
+            "MiB",
+            "-1",
+            "1 MiB",
+            "1XB",
+            "1.5",
+            "1.1B",
+            "0.1KiB",
+            "1.",
+            ".5K",
+            "1.2.3K",
+            "20000000PiB",
+            "99999999999999999999999",
+        ] {
+            assert!(s.parse::<ByteSize>().is_err(), "{}", s);
+        }
+    }
+}
@@ -0,0 +1,299 @@
+//! chrony `server` directive value
+//!
+//! https://chrony.tuxfamily.org/doc/4.0/chrony.conf.html#server
+//!
+//! ```text
+//! server hostname [option]...
+//! ```
+//!
+//! The value is the part after the `server` keyword, an address (hostname, IPv4 or IPv6)
+//! followed by options. Options are flags (`iburst`) or they have a single value
+//! (`minpoll 6`), values are range checked.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use reconfix::parser::chrony::{OptionValue, Server};
+//!
+//! let server: Server = "time.example.com iburst minpoll 4 maxdelay 0.5".parse().unwrap();
+//! assert_eq!(server.address(), "time.example.com");
+//! assert_eq!(server.option("iburst"), Some(&OptionValue::Flag));
+//! assert_eq!(server.option("minpoll"), Some(&OptionValue::Integer(4)));
+//! assert_eq!(server.option("maxdelay"), Some(&OptionValue::Float(0.5)));
+//! ```
+use std::fmt;
+use std::net::IpAddr;
+use std::str::FromStr;
+
+use crate::{
+    error::{Error, Result, ResultExt},
+    utils::net::is_valid_hostname,
+};
+
+#[derive(Debug, Clone, Copy)]
+enum Kind {
+    Flag,
+    Integer { min: i64, max: i64 },
+    Float { min: f64, max: f64 },
+    Hex { min: i64, max: i64 },
+}
+
+/// Poll interval as a power of 2 in seconds
+const POLL: Kind = Kind::Integer { min: -6, max: 24 };
+const PORT: Kind = Kind::Integer { min: 0, max: 65535 };
+const U32_MAX: i64 = u32::MAX as i64;
+
+const OPTIONS: &[(&str, Kind)] = &[
+    ("auto_offline", Kind::Flag),
+    ("burst", Kind::Flag),
+    ("copy", Kind::Flag),
+    ("iburst", Kind::Flag),
+    ("noselect", Kind::Flag),
+    ("nts", Kind::Flag),
+    ("offline", Kind::Flag),
+    ("prefer", Kind::Flag),
+    ("require", Kind::Flag),
+    ("trust", Kind::Flag),
+    ("xleave", Kind::Flag),
+    ("port", PORT),
+    ("ntsport", PORT),
+    ("minpoll", POLL),
+    ("maxpoll", POLL),
+    ("presend", POLL),
+    ("polltarget", Kind::Integer { min: 6, max: 60 }),
+    ("minsamples", Kind::Integer { min: 0, max: 64 }),
+    ("maxsamples", Kind::Integer { min: 0, max: 64 }),
+    ("filter", Kind::Integer { min: 1, max: 64 }),
+    ("key", Kind::Integer { min: 1, max: U32_MAX }),
+    ("certset", Kind::Integer { min: 0, max: U32_MAX }),
+    ("version", Kind::Integer { min: 1, max: 4 }),
+    ("minstratum", Kind::Integer { min: 0, max: 15 }),
+    ("extfield", Kind::Hex { min: 0, max: 0xffff }),
+    ("maxdelay", Kind::Float { min: 0.0, max: 1000.0 }),
+    ("maxdelayratio", Kind::Float { min: 1.0, max: 1e6 }),
+    ("maxdelaydevratio", Kind::Float { min: 0.0, max: 1e6 }),
+    ("maxdelayquant", Kind::Float { min: 0.0, max: 1.0 }),
+    ("mindelay", Kind::Float { min: 0.0, max: 1000.0 }),
+    ("asymmetry", Kind::Float { min: -1.0, max: 1.0 }),
+    ("offset", Kind::Float { min: -1e6, max: 1e6 }),
+];
+
+/// Server option value
+#[derive(Debug, Clone, PartialEq)]
+pub enum OptionValue {
+    Flag,
+    Integer(i64),
+    Float(f64),
+    Hex(i64),
+}
+
+impl fmt::Display for OptionValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OptionValue::Flag => Ok(()),
+            OptionValue::Integer(i) => write!(f, "{}", i),
+            OptionValue::Float(x) => write!(f, "{}", x),
+            OptionValue::Hex(i) => write!(f, "{:X}", i),
+        }
+    }
+}
+
+/// Parsed `server` directive value
+#[derive(Debug, Clone, PartialEq)]
+pub struct Server {
+    address: String,
+    options: Vec<(String, OptionValue)>,
+}
+
+impl Server {
+    /// Hostname, IPv4 or IPv6 address
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    /// Options in the original order
+    pub fn options(&self) -> &[(String, OptionValue)] {
+        self.options.as_slice()
+    }
+
+    /// Returns option value
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - An option name
+    pub fn option(&self, name: &str) -> Option<&OptionValue> {
+        self.options.iter().find(|(n, _)| n == name).map(|(_, v)| v)
+    }
+}
+
+fn out_of_range<T: fmt::Display>(name: &str, value: &str, min: T, max: T) -> Error {
+    Error::with_message(format!("'{}' out of range, expected {}..{}", name, min, max))
+        .context("value", value.to_string())
+}
+
+fn parse_value(name: &str, kind: Kind, value: Option<&str>) -> Result<OptionValue> {
+    match (kind, value) {
+        (Kind::Flag, _) => Ok(OptionValue::Flag),
+        (_, None) => Err(Error::with_message(format!("missing '{}' value", name))),
+        (Kind::Integer { min, max }, Some(value)) => {
+            let i: i64 = value.parse().map_err(|_| {
+                Error::with_message(format!("'{}' expects an integer", name)).context("value", value.to_string())
+            })?;
+            if i < min || i > max {
+                return Err(out_of_range(name, value, min, max));
+            }
+            Ok(OptionValue::Integer(i))
+        }
+        (Kind::Float { min, max }, Some(value)) => {
+            let x: f64 = value.parse().map_err(|_| {
+                Error::with_message(format!("'{}' expects a number", name)).context("value", value.to_string())
+            })?;
+            // NaN fails both comparisons
+            if !(x >= min && x <= max) {
+                return Err(out_of_range(name, value, min, max));
+            }
+            Ok(OptionValue::Float(x))
+        }
+        (Kind::Hex { min, max }, Some(value)) => {
+            let i = i64::from_str_radix(value, 16).map_err(|_| {
+                Error::with_message(format!("'{}' expects a hexadecimal number", name))
+                    .context("value", value.to_string())
+            })?;
+            if i < min || i > max {
+                return Err(out_of_range(name, value, format!("{:X}", min), format!("{:X}", max)));
+            }
+            Ok(OptionValue::Hex(i))
+        }
+    }
+}
+
+impl FromStr for Server {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Server> {
+        let mut tokens = s.split_whitespace();
+
+        let address = tokens.next().ok_or_else(|| Error::with_message("missing address"))?;
+        if address.parse::<IpAddr>().is_err() && !is_valid_hostname(address) {
+            return Err(Error::with_message("invalid address").context("address", address.to_string()));
+        }
+
+        let mut options: Vec<(String, OptionValue)> = vec![];
+
+        while let Some(name) = tokens.next() {
+            let kind = OPTIONS
+                .iter()
+                .find(|(n, _)| *n == name)
+                .map(|(_, k)| *k)
+                .ok_or_else(|| Error::with_message(format!("unknown option '{}'", name)))?;
+
+            if options.iter().any(|(n, _)| n == name) {
+                return Err(Error::with_message(format!("duplicate option '{}'", name)));
+            }
+
+            let value = match kind {
+                Kind::Flag => None,
+                _ => tokens.next(),
+            };
+
+            let value = parse_value(name, kind, value).context("option", name.to_string())?;
+            options.push((name.to_string(), value));
+        }
+
+        let server = Server {
+            address: address.to_string(),
+            options,
+        };
+
+        if let (Some(OptionValue::Integer(min)), Some(OptionValue::Integer(max))) =
+            (server.option("minpoll"), server.option("maxpoll"))
+        {
+            if min > max {
+                return Err(Error::with_message("minpoll must not be greater than maxpoll"));
+            }
+        }
+
+        Ok(server)
+    }
+}
+
+impl fmt::Display for Server {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.address)?;
+
+        for (name, value) in &self.options {
+            match value {
+                OptionValue::Flag => write!(f, " {}", name)?,
+                _ => write!(f, " {} {}", name, value)?,
+            };
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Result<Server> {
+        s.parse()
+    }
+
+    #[test]
+    fn address_only() {
+        for address in &["10.0.0.1", "2001:db8::1", "pool.ntp.org"] {
+            let server = parse(address).unwrap();
+            assert_eq!(server.address(), *address);
+            assert!(server.options().is_empty());
+        }
+    }
+
+    #[test]
+    fn options() {
+        let server = parse("foo.example.net iburst minpoll 6 maxpoll 10 key 25 maxdelay 0.3 asymmetry -0.5").unwrap();
+        assert_eq!(server.option("iburst"), Some(&OptionValue::Flag));
+        assert_eq!(server.option("minpoll"), Some(&OptionValue::Integer(6)));
+        assert_eq!(server.option("maxpoll"), Some(&OptionValue::Integer(10)));
+        assert_eq!(server.option("key"), Some(&OptionValue::Integer(25)));
+        assert_eq!(server.option("maxdelay"), Some(&OptionValue::Float(0.3)));
+        assert_eq!(server.option("asymmetry"), Some(&OptionValue::Float(-0.5)));
+        assert_eq!(server.option("port"), None);
+
+        let server = parse("time.example.com nts minstratum 2 extfield F323 maxdelayquant 0.2").unwrap();
+        assert_eq!(server.option("minstratum"), Some(&OptionValue::Integer(2)));
+        assert_eq!(server.option("extfield"), Some(&OptionValue::Hex(0xf323)));
+        assert_eq!(server.option("maxdelayquant"), Some(&OptionValue::Float(0.2)));
+    }
+
+    #[test]
+    fn display() {
+        let s = "foo.example.net iburst minpoll -2 port 123 maxdelay 0.3 extfield F323";
+        assert_eq!(parse(s).unwrap().to_string(), s);
+        assert_eq!(parse("  10.0.0.1   prefer ").unwrap().to_string(), "10.0.0.1 prefer");
+    }
+
+    #[test]
+    fn invalid() {
+        for s in &[
+            "",
+            "foo$@",
+            "10.0.0.1 foo",
+            "10.0.0.1 minpoll",
+            "10.0.0.1 minpoll x",
+            "10.0.0.1 minpoll 25",
+            "10.0.0.1 port 65536",
+            "10.0.0.1 key 0",
+            "10.0.0.1 version 5",
+            "10.0.0.1 minstratum 16",
+            "10.0.0.1 extfield x",
+            "10.0.0.1 extfield 10000",
+            "10.0.0.1 maxdelay -1",
+            "10.0.0.1 maxdelay NaN",
+            "10.0.0.1 minpoll 10 maxpoll 6",
+            "10.0.0.1 iburst iburst",
+        ] {
+            assert!(parse(s).is_err(), "{}", s);
+        }
+    }
+}
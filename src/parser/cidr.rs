@@ -0,0 +1,318 @@
+//! IPv4 / IPv6 network in the CIDR notation
+//!
+//! ```text
+//! address/prefix-length
+//! ```
+//!
+//! The prefix length is required and range checked per address family. Host bits must not be
+//! set, `10.0.0.1/8` is an address within a network, not a network.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use reconfix::parser::cidr::Cidr;
+//!
+//! let cidr: Cidr = "192.168.0.0/16".parse().unwrap();
+//! assert_eq!(cidr.prefix_len(), 16);
+//! assert!(cidr.is_private());
+//! assert!(cidr.contains(&"192.168.1.1".parse().unwrap()));
+//! ```
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::str::FromStr;
+
+use crate::error::{Error, Result};
+
+/// RFC 1918 & RFC 4193 (unique local) networks
+const PRIVATE: &[Cidr] = &[
+    Cidr::v4(Ipv4Addr::new(10, 0, 0, 0), 8),
+    Cidr::v4(Ipv4Addr::new(172, 16, 0, 0), 12),
+    Cidr::v4(Ipv4Addr::new(192, 168, 0, 0), 16),
+    Cidr::v6(Ipv6Addr::new(0xfc00, 0, 0, 0, 0, 0, 0, 0), 7),
+];
+
+const MULTICAST: &[Cidr] = &[
+    Cidr::v4(Ipv4Addr::new(224, 0, 0, 0), 4),
+    Cidr::v6(Ipv6Addr::new(0xff00, 0, 0, 0, 0, 0, 0, 0), 8),
+];
+
+/// Special purpose networks (RFC 6890), which are not private or multicast
+const RESERVED: &[Cidr] = &[
+    // "This" network
+    Cidr::v4(Ipv4Addr::new(0, 0, 0, 0), 8),
+    // Shared address space (carrier-grade NAT)
+    Cidr::v4(Ipv4Addr::new(100, 64, 0, 0), 10),
+    // Loopback
+    Cidr::v4(Ipv4Addr::new(127, 0, 0, 0), 8),
+    // Link local
+    Cidr::v4(Ipv4Addr::new(169, 254, 0, 0), 16),
+    // IETF protocol assignments
+    Cidr::v4(Ipv4Addr::new(192, 0, 0, 0), 24),
+    // Documentation (TEST-NET-1, 2 & 3)
+    Cidr::v4(Ipv4Addr::new(192, 0, 2, 0), 24),
+    Cidr::v4(Ipv4Addr::new(198, 51, 100, 0), 24),
+    Cidr::v4(Ipv4Addr::new(203, 0, 113, 0), 24),
+    // 6to4 relay anycast
+    Cidr::v4(Ipv4Addr::new(192, 88, 99, 0), 24),
+    // Benchmarking
+    Cidr::v4(Ipv4Addr::new(198, 18, 0, 0), 15),
+    // Reserved for future use & limited broadcast
+    Cidr::v4(Ipv4Addr::new(240, 0, 0, 0), 4),
+    // Unspecified & loopback
+    Cidr::v6(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 0), 128),
+    Cidr::v6(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1), 128),
+    // IPv4 mapped
+    Cidr::v6(Ipv6Addr::new(0, 0, 0, 0, 0, 0xffff, 0, 0), 96),
+    // IPv4-IPv6 translation
+    Cidr::v6(Ipv6Addr::new(0x64, 0xff9b, 0, 0, 0, 0, 0, 0), 96),
+    // Discard only
+    Cidr::v6(Ipv6Addr::new(0x100, 0, 0, 0, 0, 0, 0, 0), 64),
+    // IETF protocol assignments
+    Cidr::v6(Ipv6Addr::new(0x2001, 0, 0, 0, 0, 0, 0, 0), 23),
+    // Documentation
+    Cidr::v6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0), 32),
+    // Link local
+    Cidr::v6(Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 0), 10),
+];
+
+/// Returns `true` if the address is within the private address space
+pub fn is_private_address(address: &IpAddr) -> bool {
+    PRIVATE.iter().any(|x| x.contains(address))
+}
+
+/// Returns `true` if the address is within the multicast address space
+pub fn is_multicast_address(address: &IpAddr) -> bool {
+    MULTICAST.iter().any(|x| x.contains(address))
+}
+
+/// Returns `true` if the address is a special purpose one (loopback, documentation, ...)
+pub fn is_reserved_address(address: &IpAddr) -> bool {
+    RESERVED.iter().any(|x| x.contains(address))
+}
+
+/// Clears host bits
+pub(crate) fn mask(address: &IpAddr, prefix_len: u8) -> IpAddr {
+    let prefix_len = u32::from(prefix_len);
+
+    match address {
+        IpAddr::V4(ip) => {
+            let mask = u32::MAX.checked_shl(32 - prefix_len).unwrap_or(0);
+            IpAddr::V4(Ipv4Addr::from(u32::from(*ip) & mask))
+        }
+        IpAddr::V6(ip) => {
+            let mask = u128::MAX.checked_shl(128 - prefix_len).unwrap_or(0);
+            IpAddr::V6(Ipv6Addr::from(u128::from(*ip) & mask))
+        }
+    }
+}
+
+/// Maximum prefix length for the address family
+pub(crate) fn max_prefix_len(address: &IpAddr) -> u8 {
+    match address {
+        IpAddr::V4(_) => 32,
+        IpAddr::V6(_) => 128,
+    }
+}
+
+/// IP network
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cidr {
+    network: IpAddr,
+    prefix_len: u8,
+}
+
+impl Cidr {
+    const fn v4(network: Ipv4Addr, prefix_len: u8) -> Cidr {
+        Cidr {
+            network: IpAddr::V4(network),
+            prefix_len,
+        }
+    }
+
+    const fn v6(network: Ipv6Addr, prefix_len: u8) -> Cidr {
+        Cidr {
+            network: IpAddr::V6(network),
+            prefix_len,
+        }
+    }
+
+    /// Network address
+    pub fn network(&self) -> &IpAddr {
+        &self.network
+    }
+
+    pub fn prefix_len(&self) -> u8 {
+        self.prefix_len
+    }
+
+    /// Returns `true` if the address is within this network
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - An address of any family, addresses of the other family are never contained
+    pub fn contains(&self, address: &IpAddr) -> bool {
+        self.network.is_ipv4() == address.is_ipv4() && mask(address, self.prefix_len) == self.network
+    }
+
+    /// Returns `true` if the whole `other` network is within this network
+    pub fn contains_network(&self, other: &Cidr) -> bool {
+        other.prefix_len >= self.prefix_len && self.contains(&other.network)
+    }
+
+    /// Returns `true` if the network is within the private address space
+    pub fn is_private(&self) -> bool {
+        PRIVATE.iter().any(|x| x.contains_network(self))
+    }
+
+    /// Returns `true` if the network is within the multicast address space
+    pub fn is_multicast(&self) -> bool {
+        MULTICAST.iter().any(|x| x.contains_network(self))
+    }
+}
+
+impl FromStr for Cidr {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Cidr> {
+        let idx = s
+            .find('/')
+            .ok_or_else(|| Error::with_message("missing prefix length").context("cidr", s.to_string()))?;
+        let (address, prefix_len) = (&s[..idx], &s[idx + 1..]);
+
+        let address: IpAddr = address
+            .parse()
+            .map_err(|_| Error::with_message("invalid IP address").context("address", address.to_string()))?;
+
+        let max = max_prefix_len(&address);
+        let prefix_len = match prefix_len.parse::<u8>() {
+            Ok(x) if x <= max && !prefix_len.starts_with('+') => x,
+            _ => {
+                return Err(
+                    Error::with_message(format!("invalid prefix length, expected 0..{}", max))
+                        .context("prefix length", prefix_len.to_string()),
+                );
+            }
+        };
+
+        let network = mask(&address, prefix_len);
+        if network != address {
+            return Err(
+                Error::with_message(format!("host bits are set, network is '{}/{}'", network, prefix_len))
+                    .context("cidr", s.to_string()),
+            );
+        }
+
+        Ok(Cidr { network, prefix_len })
+    }
+}
+
+impl fmt::Display for Cidr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.network, self.prefix_len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Result<Cidr> {
+        s.parse()
+    }
+
+    fn address(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn networks() {
+        for s in &["0.0.0.0/0", "10.0.0.0/8", "10.1.2.3/32", "::/0", "fd00::/8", "::1/128"] {
+            assert_eq!(&parse(s).unwrap().to_string(), s);
+        }
+    }
+
+    #[test]
+    fn contains() {
+        let cidr = parse("10.0.0.0/8").unwrap();
+        assert!(cidr.contains(&address("10.255.0.1")));
+        assert!(!cidr.contains(&address("11.0.0.1")));
+        assert!(!cidr.contains(&address("::a00:1")));
+        assert!(parse("0.0.0.0/0").unwrap().contains(&address("1.2.3.4")));
+        assert!(!parse("0.0.0.0/0").unwrap().contains(&address("::1")));
+
+        assert!(cidr.contains_network(&parse("10.1.0.0/16").unwrap()));
+        assert!(!parse("10.1.0.0/16").unwrap().contains_network(&cidr));
+    }
+
+    #[test]
+    fn private_and_multicast() {
+        assert!(parse("10.1.0.0/16").unwrap().is_private());
+        assert!(parse("172.31.0.0/16").unwrap().is_private());
+        assert!(parse("fd12:3456::/32").unwrap().is_private());
+        assert!(!parse("172.32.0.0/16").unwrap().is_private());
+        assert!(!parse("0.0.0.0/0").unwrap().is_private());
+
+        assert!(parse("239.0.0.0/8").unwrap().is_multicast());
+        assert!(parse("ff02::/16").unwrap().is_multicast());
+        assert!(!parse("10.0.0.0/8").unwrap().is_multicast());
+    }
+
+    #[test]
+    fn address_classes() {
+        assert!(is_private_address(&address("192.168.1.1")));
+        assert!(is_private_address(&address("fd00::1")));
+        assert!(!is_private_address(&address("8.8.8.8")));
+
+        assert!(is_multicast_address(&address("224.0.0.251")));
+        assert!(is_multicast_address(&address("ff02::fb")));
+        assert!(!is_multicast_address(&address("10.0.0.1")));
+
+        for s in &[
+            "0.0.0.0",
+            "100.64.0.1",
+            "127.0.0.1",
+            "169.254.1.1",
+            "192.0.2.1",
+            "192.88.99.1",
+            "255.255.255.255",
+            "::",
+            "::1",
+            "::ffff:10.0.0.1",
+            "64:ff9b::808:808",
+            "2001::1",
+            "2001:1ff::1",
+            "2001:db8::1",
+            "fe80::1",
+        ] {
+            assert!(is_reserved_address(&address(s)), "{}", s);
+        }
+        for s in &[
+            "8.8.8.8",
+            "10.0.0.1",
+            "100.128.0.1",
+            "2001:200::1",
+            "2606:4700::1111",
+            "fd00::1",
+        ] {
+            assert!(!is_reserved_address(&address(s)), "{}", s);
+        }
+    }
+
+    #[test]
+    fn invalid() {
+        for s in &[
+            "",
+            "10.0.0.0",
+            "10.0.0.0/",
+            "10.0.0.0/33",
+            "10.0.0.0/+8",
+            "10.0.0.0/255.0.0.0",
+            "10.0.0.1/8",
+            "fd00::/129",
+            "fd00::1/64",
+            "foo/8",
+        ] {
+            assert!(parse(s).is_err(), "{}", s);
+        }
+    }
+}
@@ -0,0 +1,355 @@
+//! dnsmasq `--server` value
+//!
+//! http://www.thekelleys.org.uk/dnsmasq/docs/dnsmasq-man.html
+//!
+//! ```text
+//! --server=[/[<domain>]/[domain/]][<ipaddr>[#<port>][@<source-ip>|<interface>[#<port>]]
+//! ```
+//!
+//! # Examples
+//!
+//! ```rust
+//! use reconfix::parser::dnsmasq::{Server, Upstream};
+//!
+//! let server: Server = "/lan/10.0.0.1#5353@eth0".parse().unwrap();
+//! assert_eq!(server.domains(), &["lan".to_string()]);
+//! assert_eq!(server.upstream().address().unwrap().to_string(), "10.0.0.1");
+//! assert_eq!(server.upstream().port(), Some(5353));
+//! assert_eq!(server.to_string(), "/lan/10.0.0.1#5353@eth0");
+//!
+//! let server: Server = "/local/".parse().unwrap();
+//! assert_eq!(server.upstream(), &Upstream::Local);
+//! ```
+use std::fmt;
+use std::net::IpAddr;
+use std::str::FromStr;
+
+use crate::{
+    error::{Error, Result, ResultExt},
+    utils::net::is_valid_interface_name,
+};
+
+/// Upstream server
+#[derive(Debug, Clone, PartialEq)]
+pub enum Upstream {
+    /// Domains are answered from `/etc/hosts` or DHCP only (`/domain/`)
+    Local,
+    /// Domains are forwarded to the standard servers (`/domain/#`)
+    Standard,
+    /// Server address with an optional port
+    Address { address: IpAddr, port: Option<u16> },
+}
+
+impl Upstream {
+    pub fn address(&self) -> Option<&IpAddr> {
+        match self {
+            Upstream::Address { address, .. } => Some(address),
+            _ => None,
+        }
+    }
+
+    pub fn port(&self) -> Option<u16> {
+        match self {
+            Upstream::Address { port, .. } => *port,
+            _ => None,
+        }
+    }
+}
+
+/// Source of queries sent to the upstream server
+#[derive(Debug, Clone, PartialEq)]
+pub enum Source {
+    /// Source address with an optional port
+    Address { address: IpAddr, port: Option<u16> },
+    /// Interface name with an optional port
+    Interface { interface: String, port: Option<u16> },
+}
+
+/// Parsed `--server` value
+#[derive(Debug, Clone, PartialEq)]
+pub struct Server {
+    domains: Vec<String>,
+    upstream: Upstream,
+    source: Option<Source>,
+}
+
+impl Server {
+    /// Domains this server is used for, empty for all domains
+    ///
+    /// An empty domain (`//`) stands for unqualified names.
+    pub fn domains(&self) -> &[String] {
+        self.domains.as_slice()
+    }
+
+    pub fn upstream(&self) -> &Upstream {
+        &self.upstream
+    }
+
+    pub fn source(&self) -> Option<&Source> {
+        self.source.as_ref()
+    }
+}
+
+fn is_valid_domain(domain: &str) -> bool {
+    // Wildcard domains are supported since dnsmasq 2.86
+    let domain = domain.strip_prefix("*.").unwrap_or(domain);
+
+    domain.len() <= 255
+        && domain.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+        })
+}
+
+fn is_valid_interface(interface: &str) -> bool {
+    // `#` & `@` are valid in Linux, but not here, they're part of the syntax
+    is_valid_interface_name(interface) && !interface.contains(['#', '@'])
+}
+
+/// Splits `<value>[#<port>]` and parses the port
+fn split_port(s: &str) -> Result<(&str, Option<u16>)> {
+    let (value, port) = match s.find('#') {
+        Some(idx) => (&s[..idx], Some(&s[idx + 1..])),
+        None => (s, None),
+    };
+
+    let port = match port {
+        Some(port) => Some(
+            port.parse()
+                .map_err(|_| Error::with_message("invalid port").context("port", port.to_string()))?,
+        ),
+        None => None,
+    };
+
+    Ok((value, port))
+}
+
+/// Parses `<ipaddr>[#<port>]`
+fn parse_address(s: &str) -> Result<(IpAddr, Option<u16>)> {
+    let (address, port) = split_port(s)?;
+
+    let address = address
+        .parse()
+        .map_err(|_| Error::with_message("invalid IP address").context("address", address.to_string()))?;
+
+    Ok((address, port))
+}
+
+fn parse_upstream(s: &str, has_domains: bool) -> Result<Upstream> {
+    match s {
+        "" if has_domains => Ok(Upstream::Local),
+        "#" if has_domains => Ok(Upstream::Standard),
+        "" => Err(Error::with_message("missing server address")),
+        _ => {
+            let (address, port) = parse_address(s)?;
+            if port == Some(0) {
+                return Err(Error::with_message("invalid port").context("port", "0"));
+            }
+            Ok(Upstream::Address { address, port })
+        }
+    }
+}
+
+fn parse_source(s: &str) -> Result<Source> {
+    let (value, port) = split_port(s)?;
+
+    if let Ok(address) = value.parse() {
+        return Ok(Source::Address { address, port });
+    }
+
+    if !is_valid_interface(value) {
+        return Err(Error::with_message("invalid source address or interface").context("source", s.to_string()));
+    }
+
+    Ok(Source::Interface {
+        interface: value.to_string(),
+        port,
+    })
+}
+
+impl FromStr for Server {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Server> {
+        let (domains, rest) = match s.strip_prefix('/') {
+            Some(value) => {
+                let idx = value
+                    .rfind('/')
+                    .ok_or_else(|| Error::with_message("missing '/' after domain"))?;
+                let domains: Vec<String> = value[..idx].split('/').map(String::from).collect();
+
+                if let Some(domain) = domains.iter().find(|x| !x.is_empty() && !is_valid_domain(x)) {
+                    return Err(Error::with_message("invalid domain").context("domain", domain.clone()));
+                }
+
+                (domains, &value[idx + 1..])
+            }
+            None => (vec![], s),
+        };
+
+        let (upstream, source) = match rest.find('@') {
+            Some(idx) => (&rest[..idx], Some(&rest[idx + 1..])),
+            None => (rest, None),
+        };
+
+        let upstream = parse_upstream(upstream, !domains.is_empty())?;
+
+        let source = match source {
+            Some(_) if upstream.address().is_none() => {
+                return Err(Error::with_message("source requires server address"));
+            }
+            Some(source) => Some(parse_source(source)?),
+            None => None,
+        };
+
+        Ok(Server {
+            domains,
+            upstream,
+            source,
+        })
+    }
+}
+
+impl fmt::Display for Server {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !self.domains.is_empty() {
+            write!(f, "/{}/", self.domains.join("/"))?;
+        }
+
+        match &self.upstream {
+            Upstream::Local => {}
+            Upstream::Standard => write!(f, "#")?,
+            Upstream::Address { address, port } => {
+                write!(f, "{}", address)?;
+                if let Some(port) = port {
+                    write!(f, "#{}", port)?;
+                }
+            }
+        };
+
+        let (source, port) = match &self.source {
+            Some(Source::Address { address, port }) => (address.to_string(), port),
+            Some(Source::Interface { interface, port }) => (interface.clone(), port),
+            None => return Ok(()),
+        };
+
+        write!(f, "@{}", source)?;
+        if let Some(port) = port {
+            write!(f, "#{}", port)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses dnsmasq `--server` value
+///
+/// Same as `s.parse::<Server>()`, but the error contains the value.
+pub fn parse_server(s: &str) -> Result<Server> {
+    s.parse().context("server", s.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn address(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn plain_address() {
+        let server = parse_server("10.0.0.1").unwrap();
+        assert!(server.domains().is_empty());
+        assert_eq!(
+            server.upstream(),
+            &Upstream::Address {
+                address: address("10.0.0.1"),
+                port: None
+            }
+        );
+        assert_eq!(server.source(), None);
+    }
+
+    #[test]
+    fn ipv6_address_with_port() {
+        let server = parse_server("2001:db8::1#5353").unwrap();
+        assert_eq!(server.upstream().address(), Some(&address("2001:db8::1")));
+        assert_eq!(server.upstream().port(), Some(5353));
+    }
+
+    #[test]
+    fn domains() {
+        let server = parse_server("/lan/home.arpa/10.0.0.1").unwrap();
+        assert_eq!(server.domains(), &["lan".to_string(), "home.arpa".to_string()]);
+
+        let server = parse_server("//10.0.0.1").unwrap();
+        assert_eq!(server.domains(), &["".to_string()]);
+
+        let server = parse_server("/*.example.com/10.0.0.1").unwrap();
+        assert_eq!(server.domains(), &["*.example.com".to_string()]);
+    }
+
+    #[test]
+    fn local_and_standard() {
+        assert_eq!(parse_server("/local/").unwrap().upstream(), &Upstream::Local);
+        assert_eq!(parse_server("/google.com/#").unwrap().upstream(), &Upstream::Standard);
+    }
+
+    #[test]
+    fn source() {
+        let server = parse_server("10.0.0.1@192.168.1.1#1053").unwrap();
+        assert_eq!(
+            server.source(),
+            Some(&Source::Address {
+                address: address("192.168.1.1"),
+                port: Some(1053)
+            })
+        );
+
+        let server = parse_server("10.0.0.1@eth0").unwrap();
+        assert_eq!(
+            server.source(),
+            Some(&Source::Interface {
+                interface: "eth0".to_string(),
+                port: None
+            })
+        );
+    }
+
+    #[test]
+    fn display() {
+        for s in &[
+            "10.0.0.1",
+            "/lan/10.0.0.1#5353@eth0",
+            "//10.0.0.1",
+            "/a/b/#",
+            "/local/",
+            "::1@::2#53",
+            "10.0.0.1@eth0#53",
+        ] {
+            assert_eq!(&parse_server(s).unwrap().to_string(), s);
+        }
+    }
+
+    #[test]
+    fn invalid() {
+        for s in &[
+            "",
+            "#",
+            "foo.bar",
+            "10.0.0.1#0",
+            "10.0.0.1#70000",
+            "/lan",
+            "/-lan/10.0.0.1",
+            "/lan/#@eth0",
+            "10.0.0.1@",
+            "10.0.0.1@eth0 eth1",
+        ] {
+            assert!(parse_server(s).is_err(), "{}", s);
+        }
+    }
+}
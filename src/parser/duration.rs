@@ -0,0 +1,340 @@
+//! Duration
+//!
+//! Two notations are accepted:
+//!
+//! ```text
+//! 1h30m, 30s, 1.5h, 250ms, 2d    sequence of <number><unit>, units are d, h, m, s, ms, us (µs) & ns
+//! PT1H30M, P2DT12H, P1W          ISO 8601 duration
+//! ```
+//!
+//! ISO 8601 years & months are not supported, because their length varies. Negative durations
+//! are not supported either.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use reconfix::parser::duration::Duration;
+//!
+//! let duration: Duration = "PT1H30M".parse().unwrap();
+//! assert_eq!(duration.as_std().as_secs(), 5400);
+//! assert_eq!(duration.to_string(), "1h30m");
+//! assert_eq!(duration, "90m".parse().unwrap());
+//! ```
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+use std::time;
+
+use crate::error::{Error, Result};
+
+const NANOS_PER_SEC: u128 = 1_000_000_000;
+const NANOS_PER_MINUTE: u128 = 60 * NANOS_PER_SEC;
+const NANOS_PER_HOUR: u128 = 60 * NANOS_PER_MINUTE;
+const NANOS_PER_DAY: u128 = 24 * NANOS_PER_HOUR;
+const NANOS_PER_WEEK: u128 = 7 * NANOS_PER_DAY;
+
+const UNITS: &[(&str, u128)] = &[
+    ("d", NANOS_PER_DAY),
+    ("h", NANOS_PER_HOUR),
+    ("m", NANOS_PER_MINUTE),
+    ("s", NANOS_PER_SEC),
+    ("ms", 1_000_000),
+    ("us", 1_000),
+    ("µs", 1_000),
+    ("ns", 1),
+];
+
+/// Units used by `Display`
+const DISPLAY_UNITS: &[(&str, u128)] = &[
+    ("h", NANOS_PER_HOUR),
+    ("m", NANOS_PER_MINUTE),
+    ("s", NANOS_PER_SEC),
+    ("ms", 1_000_000),
+    ("us", 1_000),
+    ("ns", 1),
+];
+
+/// Non negative duration with the nanosecond precision
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Duration(time::Duration);
+
+impl Duration {
+    pub fn as_std(&self) -> time::Duration {
+        self.0
+    }
+
+    pub fn as_secs_f64(&self) -> f64 {
+        self.0.as_secs_f64()
+    }
+
+    /// Creates duration from seconds
+    ///
+    /// # Arguments
+    ///
+    /// * `secs` - A number of seconds, must be finite & non negative
+    pub fn from_secs_f64(secs: f64) -> Result<Duration> {
+        if !secs.is_finite() || secs < 0.0 {
+            return Err(Error::with_message(
+                "duration must be a finite, non negative, number of seconds",
+            ));
+        }
+        time::Duration::try_from_secs_f64(secs)
+            .map(Duration)
+            .map_err(|_| Error::with_message("duration is too long"))
+    }
+
+    fn from_nanos(nanos: u128) -> Result<Duration> {
+        let secs = u64::try_from(nanos / NANOS_PER_SEC).map_err(|_| Error::with_message("duration is too long"))?;
+        Ok(Duration(time::Duration::new(secs, (nanos % NANOS_PER_SEC) as u32)))
+    }
+}
+
+/// Converts `<integer>[.<fraction>]` number of units to nanoseconds
+fn nanos(number: &str, unit: u128) -> Result<u128> {
+    let invalid = || Error::with_message("invalid number").context("number", number.to_string());
+
+    let (integer, fraction) = match number.find(['.', ',']) {
+        Some(idx) if idx + 1 < number.len() => (&number[..idx], &number[idx + 1..]),
+        Some(_) => return Err(invalid()),
+        None => (number, ""),
+    };
+
+    if integer.is_empty() || !integer.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit()) {
+        return Err(invalid());
+    }
+
+    let too_long = || Error::with_message("duration is too long");
+
+    let integer: u128 = integer.parse().map_err(|_| too_long())?;
+    let mut result = integer.checked_mul(unit).ok_or_else(too_long)?;
+
+    // Digits beyond the nanosecond precision are ignored
+    let mut scale = unit;
+    for digit in fraction.chars() {
+        scale /= 10;
+        result += u128::from(digit.to_digit(10).expect("ascii digit")) * scale;
+    }
+
+    Ok(result)
+}
+
+/// Splits `<number><designator>...` into (number, designator) pairs
+fn components(s: &str) -> Result<Vec<(&str, &str)>> {
+    let mut result = vec![];
+    let mut rest = s;
+
+    while !rest.is_empty() {
+        let number_len = rest
+            .find(|c: char| !c.is_ascii_digit() && c != '.' && c != ',')
+            .ok_or_else(|| Error::with_message("missing unit").context("duration", s.to_string()))?;
+        let unit_len = rest[number_len..]
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(rest.len() - number_len);
+
+        if number_len == 0 {
+            return Err(Error::with_message("missing number").context("duration", s.to_string()));
+        }
+
+        result.push((&rest[..number_len], &rest[number_len..number_len + unit_len]));
+        rest = &rest[number_len + unit_len..];
+    }
+
+    Ok(result)
+}
+
+fn parse_units(s: &str) -> Result<u128> {
+    let mut total: u128 = 0;
+    let mut seen: Vec<u128> = vec![];
+
+    for (number, name) in components(s)? {
+        let unit = UNITS
+            .iter()
+            .find(|(x, _)| *x == name)
+            .map(|(_, nanos)| *nanos)
+            .ok_or_else(|| Error::with_message(format!("unknown unit '{}'", name)))?;
+
+        // `us` & `µs` are the same unit
+        if seen.contains(&unit) {
+            return Err(Error::with_message(format!("duplicate unit '{}'", name)).context("duration", s.to_string()));
+        }
+        seen.push(unit);
+
+        total = total
+            .checked_add(nanos(number, unit)?)
+            .ok_or_else(|| Error::with_message("duration is too long"))?;
+    }
+
+    Ok(total)
+}
+
+fn parse_iso8601(s: &str) -> Result<u128> {
+    let invalid = || Error::with_message("invalid ISO 8601 duration").context("duration", s.to_string());
+
+    let rest = &s[1..];
+    let (date, time) = match rest.find('T') {
+        Some(idx) => (&rest[..idx], Some(&rest[idx + 1..])),
+        None => (rest, None),
+    };
+
+    if (date.is_empty() && time.is_none()) || time == Some("") {
+        return Err(invalid());
+    }
+
+    let mut total: u128 = 0;
+
+    // Designators must be in this order & can't repeat
+    let groups: [(&str, &[(&str, u128)]); 2] = [
+        (date, &[("W", NANOS_PER_WEEK), ("D", NANOS_PER_DAY)]),
+        (
+            time.unwrap_or_default(),
+            &[("H", NANOS_PER_HOUR), ("M", NANOS_PER_MINUTE), ("S", NANOS_PER_SEC)],
+        ),
+    ];
+
+    for (idx, (value, designators)) in groups.iter().enumerate() {
+        let mut position = 0;
+
+        for (number, designator) in components(value)? {
+            if idx == 0 && (designator == "Y" || designator == "M") {
+                return Err(Error::with_message(
+                    "ISO 8601 years & months are not supported, their length varies",
+                ));
+            }
+
+            let skipped = designators[position..]
+                .iter()
+                .position(|(name, _)| *name == designator)
+                .ok_or_else(invalid)?;
+            position += skipped + 1;
+
+            total = total
+                .checked_add(nanos(number, designators[position - 1].1)?)
+                .ok_or_else(|| Error::with_message("duration is too long"))?;
+        }
+    }
+
+    Ok(total)
+}
+
+impl FromStr for Duration {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Duration> {
+        if s.is_empty() {
+            return Err(Error::with_message("empty duration"));
+        }
+
+        let nanos = if s.starts_with('P') {
+            parse_iso8601(s)?
+        } else {
+            parse_units(s)?
+        };
+
+        Duration::from_nanos(nanos)
+    }
+}
+
+impl fmt::Display for Duration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut nanos = self.0.as_nanos();
+
+        if nanos == 0 {
+            return write!(f, "0s");
+        }
+
+        for (name, unit) in DISPLAY_UNITS {
+            if nanos >= *unit {
+                write!(f, "{}{}", nanos / unit, name)?;
+                nanos %= unit;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secs(s: &str) -> f64 {
+        s.parse::<Duration>().unwrap().as_secs_f64()
+    }
+
+    #[test]
+    fn units() {
+        assert_eq!(secs("30s"), 30.0);
+        assert_eq!(secs("1h30m"), 5400.0);
+        assert_eq!(secs("1.5h"), 5400.0);
+        assert_eq!(secs("2d"), 172_800.0);
+        assert_eq!(secs("250ms"), 0.25);
+        assert_eq!(secs("1m0.5s"), 60.5);
+        assert_eq!(secs("0s"), 0.0);
+        assert_eq!("1µs".parse::<Duration>().unwrap().as_std().as_nanos(), 1000);
+        assert_eq!("1ns".parse::<Duration>().unwrap().as_std().as_nanos(), 1);
+    }
+
+    #[test]
+    fn iso8601() {
+        assert_eq!(secs("PT30S"), 30.0);
+        assert_eq!(secs("PT1H30M"), 5400.0);
+        assert_eq!(secs("P1D"), 86_400.0);
+        assert_eq!(secs("P2DT12H"), 216_000.0);
+        assert_eq!(secs("P1W"), 604_800.0);
+        assert_eq!(secs("PT0.5S"), 0.5);
+        assert_eq!(secs("PT0,5S"), 0.5);
+    }
+
+    #[test]
+    fn from_secs() {
+        assert_eq!(Duration::from_secs_f64(1.5).unwrap().as_secs_f64(), 1.5);
+        assert!(Duration::from_secs_f64(-1.0).is_err());
+        assert!(Duration::from_secs_f64(f64::NAN).is_err());
+        assert!(Duration::from_secs_f64(1e30).is_err());
+    }
+
+    #[test]
+    fn display() {
+        for (s, expected) in &[
+            ("PT1H30M", "1h30m"),
+            ("90m", "1h30m"),
+            ("2d", "48h"),
+            ("1.5s", "1s500ms"),
+            ("0h", "0s"),
+            ("1001ns", "1us1ns"),
+        ] {
+            assert_eq!(&s.parse::<Duration>().unwrap().to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn invalid() {
+        for s in &[
+            "",
+            "30",
+            "s",
+            "-30s",
+            "30 s",
+            "30x",
+            "1.s",
+            ".5s",
+            "1..5s",
+            "P",
+            "PT",
+            "P1Y",
+            "P1M",
+            "PT1D",
+            "P1H",
+            "PT1M1H",
+            "PT1H1H",
+            "1s1s",
+            "1h30m1h",
+            "1us1µs",
+            "P1DT",
+            "pt1h",
+            "99999999999999999999999d",
+        ] {
+            assert!(s.parse::<Duration>().is_err(), "{}", s);
+        }
+    }
+}
@@ -0,0 +1,214 @@
+//! iptables `--source` / `--destination` value
+//!
+//! https://linux.die.net/man/8/iptables
+//!
+//! ```text
+//! -d, --destination address[/mask][,...]
+//! ```
+//!
+//! The mask can be a prefix length (`/24`) or, for IPv4 only, a plain mask (`/255.255.255.0`).
+//! The prefix length is range checked per address family (`0..32` for IPv4, `0..128` for IPv6).
+//!
+//! # Examples
+//!
+//! ```rust
+//! use reconfix::parser::iptables::{parse_addresses, Address};
+//!
+//! let address: Address = "10.0.0.1/255.255.255.0".parse().unwrap();
+//! assert_eq!(address.prefix_len(), Some(24));
+//! assert!(address.has_host_bits());
+//! assert_eq!(address.network().to_string(), "10.0.0.0/24");
+//!
+//! let addresses = parse_addresses("10.0.0.0/8,fd00::/8").unwrap();
+//! assert_eq!(addresses.len(), 2);
+//! ```
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr};
+use std::str::FromStr;
+
+use crate::{
+    error::{Error, Result, ResultExt},
+    parser::cidr::{mask, max_prefix_len},
+};
+
+/// Address with an optional mask
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Address {
+    address: IpAddr,
+    prefix_len: Option<u8>,
+}
+
+impl Address {
+    pub fn address(&self) -> &IpAddr {
+        &self.address
+    }
+
+    /// Prefix length, `None` if there's no mask
+    pub fn prefix_len(&self) -> Option<u8> {
+        self.prefix_len
+    }
+
+    /// Maximum prefix length for the address family
+    pub fn max_prefix_len(&self) -> u8 {
+        max_prefix_len(&self.address)
+    }
+
+    /// Returns `true` if some bits outside of the mask are set (`10.0.0.1/8`)
+    ///
+    /// iptables silently clears them, which usually hides a typo.
+    pub fn has_host_bits(&self) -> bool {
+        self.network().address != self.address
+    }
+
+    /// Returns the address with host bits cleared
+    pub fn network(&self) -> Address {
+        match self.prefix_len {
+            Some(prefix_len) => Address {
+                address: mask(&self.address, prefix_len),
+                prefix_len: self.prefix_len,
+            },
+            None => *self,
+        }
+    }
+}
+
+/// Converts plain IPv4 mask (`255.255.0.0`) to the prefix length
+fn ipv4_mask_prefix_len(mask: Ipv4Addr) -> Option<u8> {
+    let mask = u32::from(mask);
+    let prefix_len = mask.leading_ones();
+
+    // Mask bits must be contiguous
+    if mask.checked_shl(prefix_len).unwrap_or(0) != 0 {
+        return None;
+    }
+
+    Some(prefix_len as u8)
+}
+
+fn parse_mask(address: &IpAddr, mask: &str) -> Result<u8> {
+    let max = max_prefix_len(address);
+
+    if let Ok(prefix_len) = mask.parse::<u8>() {
+        if prefix_len > max {
+            return Err(Error::with_message(format!("mask out of range, expected 0..{}", max))
+                .context("mask", mask.to_string()));
+        }
+        return Ok(prefix_len);
+    }
+
+    match (address, mask.parse::<Ipv4Addr>()) {
+        (IpAddr::V4(_), Ok(x)) => ipv4_mask_prefix_len(x)
+            .ok_or_else(|| Error::with_message("non-contiguous mask").context("mask", mask.to_string())),
+        _ => Err(Error::with_message("invalid mask").context("mask", mask.to_string())),
+    }
+}
+
+impl FromStr for Address {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Address> {
+        let (address, mask) = match s.find('/') {
+            Some(idx) => (&s[..idx], Some(&s[idx + 1..])),
+            None => (s, None),
+        };
+
+        let address: IpAddr = address
+            .parse()
+            .map_err(|_| Error::with_message("invalid IP address").context("address", address.to_string()))?;
+
+        let prefix_len = match mask {
+            Some(mask) => Some(parse_mask(&address, mask)?),
+            None => None,
+        };
+
+        Ok(Address { address, prefix_len })
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.address)?;
+        if let Some(prefix_len) = self.prefix_len {
+            write!(f, "/{}", prefix_len)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses comma separated list of addresses
+pub fn parse_addresses(s: &str) -> Result<Vec<Address>> {
+    s.split(',')
+        .map(|x| x.parse().context("address", x.to_string()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Result<Address> {
+        s.parse()
+    }
+
+    #[test]
+    fn plain_address() {
+        let address = parse("10.0.0.1").unwrap();
+        assert_eq!(address.prefix_len(), None);
+        assert!(!address.has_host_bits());
+
+        let address = parse("2001:db8::1").unwrap();
+        assert_eq!(address.max_prefix_len(), 128);
+        assert!(!address.has_host_bits());
+    }
+
+    #[test]
+    fn prefix_len() {
+        assert_eq!(parse("10.0.0.0/0").unwrap().prefix_len(), Some(0));
+        assert_eq!(parse("10.0.0.0/32").unwrap().prefix_len(), Some(32));
+        assert_eq!(parse("fd00::/128").unwrap().prefix_len(), Some(128));
+        assert_eq!(parse("10.0.0.0/255.0.0.0").unwrap().prefix_len(), Some(8));
+        assert_eq!(parse("10.0.0.0/0.0.0.0").unwrap().prefix_len(), Some(0));
+        assert_eq!(parse("10.0.0.0/255.255.255.255").unwrap().prefix_len(), Some(32));
+    }
+
+    #[test]
+    fn host_bits() {
+        assert!(parse("10.0.0.1/8").unwrap().has_host_bits());
+        assert!(!parse("10.0.0.0/8").unwrap().has_host_bits());
+        assert!(!parse("10.0.0.1/32").unwrap().has_host_bits());
+        assert!(parse("1.2.3.4/0").unwrap().has_host_bits());
+        assert!(parse("fd00::1/64").unwrap().has_host_bits());
+        assert!(!parse("fd00::/8").unwrap().has_host_bits());
+        assert_eq!(parse("fd12:3456::1/16").unwrap().network().to_string(), "fd12::/16");
+    }
+
+    #[test]
+    fn list() {
+        let addresses = parse_addresses("10.0.0.0/8,192.168.1.1,::1/128").unwrap();
+        assert_eq!(
+            addresses.iter().map(|x| x.to_string()).collect::<Vec<_>>(),
+            vec!["10.0.0.0/8", "192.168.1.1", "::1/128"]
+        );
+
+        assert!(parse_addresses("10.0.0.0/8,").is_err());
+        assert!(parse_addresses("10.0.0.0/8, 10.0.0.1").is_err());
+    }
+
+    #[test]
+    fn invalid() {
+        for s in &[
+            "",
+            "/8",
+            "10.0.0.0/",
+            "10.0.0.0/33",
+            "10.0.0.0/-1",
+            "fd00::/129",
+            "fd00::/255.0.0.0",
+            "10.0.0.0/255.0.255.0",
+            "10.0.0.0/8/8",
+            "foo.bar/8",
+        ] {
+            assert!(parse(s).is_err(), "{}", s);
+        }
+    }
+}
@@ -0,0 +1,150 @@
+//! MAC (EUI-48) address
+//!
+//! Common notations are accepted, hexadecimal digits are case insensitive:
+//!
+//! ```text
+//! 00:1a:2b:3c:4d:5e
+//! 00-1A-2B-3C-4D-5E
+//! 001a.2b3c.4d5e
+//! ```
+//!
+//! The canonical format, used by `Display`, is lowercase and colon separated.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use reconfix::parser::mac::MacAddress;
+//!
+//! let mac: MacAddress = "00-1A-2B-3C-4D-5E".parse().unwrap();
+//! assert_eq!(mac.to_string(), "00:1a:2b:3c:4d:5e");
+//! assert!(!mac.is_multicast());
+//! ```
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::{Error, Result};
+
+/// MAC address
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MacAddress([u8; 6]);
+
+impl MacAddress {
+    pub fn octets(&self) -> [u8; 6] {
+        self.0
+    }
+
+    /// Returns `true` if the individual/group bit is set
+    pub fn is_multicast(&self) -> bool {
+        self.0[0] & 0x01 != 0
+    }
+
+    /// Returns `true` if the universal/local bit is set
+    ///
+    /// Locally administered addresses are not assigned by the manufacturer (randomized,
+    /// virtual interfaces, ...).
+    pub fn is_local(&self) -> bool {
+        self.0[0] & 0x02 != 0
+    }
+}
+
+/// Splits the address into groups of hexadecimal digits with the expected group length
+fn groups(s: &str) -> Option<(Vec<&str>, usize)> {
+    if s.contains(':') {
+        Some((s.split(':').collect(), 2))
+    } else if s.contains('-') {
+        Some((s.split('-').collect(), 2))
+    } else if s.contains('.') {
+        Some((s.split('.').collect(), 4))
+    } else {
+        None
+    }
+}
+
+impl FromStr for MacAddress {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<MacAddress> {
+        let invalid = || Error::with_message("invalid MAC address").context("address", s.to_string());
+
+        let (groups, len) = groups(s).ok_or_else(invalid)?;
+
+        if groups.len() * len != 12 || groups.iter().any(|x| x.len() != len) {
+            return Err(invalid());
+        }
+
+        let digits = groups.concat();
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(invalid());
+        }
+
+        let mut octets = [0; 6];
+
+        for (idx, octet) in octets.iter_mut().enumerate() {
+            *octet = u8::from_str_radix(&digits[idx * 2..idx * 2 + 2], 16).map_err(|_| invalid())?;
+        }
+
+        Ok(MacAddress(octets))
+    }
+}
+
+impl fmt::Display for MacAddress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let o = &self.0;
+        write!(
+            f,
+            "{:02x}:{:02x}:{:02x}:{:02x}:{:02x}:{:02x}",
+            o[0], o[1], o[2], o[3], o[4], o[5]
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Result<MacAddress> {
+        s.parse()
+    }
+
+    #[test]
+    fn notations() {
+        for s in &[
+            "00:1a:2b:3c:4d:5e",
+            "00:1A:2B:3C:4D:5E",
+            "00-1a-2b-3c-4d-5e",
+            "001a.2b3c.4d5e",
+        ] {
+            let mac = parse(s).unwrap();
+            assert_eq!(mac.octets(), [0x00, 0x1a, 0x2b, 0x3c, 0x4d, 0x5e], "{}", s);
+            assert_eq!(mac.to_string(), "00:1a:2b:3c:4d:5e");
+        }
+    }
+
+    #[test]
+    fn bits() {
+        assert!(parse("01:00:5e:00:00:01").unwrap().is_multicast());
+        assert!(parse("ff:ff:ff:ff:ff:ff").unwrap().is_multicast());
+        assert!(!parse("00:1a:2b:3c:4d:5e").unwrap().is_multicast());
+        assert!(parse("02:42:ac:11:00:02").unwrap().is_local());
+        assert!(!parse("00:1a:2b:3c:4d:5e").unwrap().is_local());
+    }
+
+    #[test]
+    fn invalid() {
+        for s in &[
+            "",
+            "001a2b3c4d5e",
+            "00:1a:2b:3c:4d",
+            "00:1a:2b:3c:4d:5e:6f",
+            "00:1a:2b:3c:4d:5g",
+            "0:1a:2b:3c:4d:5e0",
+            "00:1a-2b:3c:4d:5e",
+            "001a.2b3c.4d5",
+            "+0:1a:2b:3c:4d:5e",
+            "00:1a:2b:3c:4d:é",
+            "001a.2b3c.4dé",
+        ] {
+            assert!(parse(s).is_err(), "{}", s);
+        }
+    }
+}
@@ -0,0 +1,14 @@
+//! Parsers for the complex string values
+//!
+//! Some types (`dnsmasq-address`, `iptables-address`, ...) are strings with their own syntax.
+//! Parsers are used by the validator and they're public, so that mappings can work with
+//! structured components instead of the raw string.
+pub mod bytesize;
+pub mod chrony;
+pub mod cidr;
+pub mod dnsmasq;
+pub mod duration;
+pub mod iptables;
+pub mod mac;
+pub mod semver;
+pub mod stringlist;
@@ -0,0 +1,294 @@
+//! Semantic version
+//!
+//! https://semver.org/spec/v2.0.0.html
+//!
+//! ```text
+//! <major>.<minor>.<patch>[-<pre-release>][+<build>]
+//! ```
+//!
+//! Versions are compared by the precedence, build metadata is ignored, `1.0.0+a` is equal
+//! to `1.0.0+b`.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use reconfix::parser::semver::Version;
+//!
+//! let version: Version = "1.2.3-rc.1+build.5".parse().unwrap();
+//! assert_eq!((version.major(), version.minor(), version.patch()), (1, 2, 3));
+//! assert!(version < "1.2.3".parse().unwrap());
+//! assert!(version > "1.2.3-beta".parse().unwrap());
+//! ```
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::{Error, Result};
+
+/// Pre-release identifier
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Identifier {
+    Numeric(u64),
+    AlphaNumeric(String),
+}
+
+impl Ord for Identifier {
+    fn cmp(&self, other: &Identifier) -> Ordering {
+        match (self, other) {
+            (Identifier::Numeric(a), Identifier::Numeric(b)) => a.cmp(b),
+            (Identifier::AlphaNumeric(a), Identifier::AlphaNumeric(b)) => a.cmp(b),
+            // Numeric identifiers have lower precedence
+            (Identifier::Numeric(_), _) => Ordering::Less,
+            (_, Identifier::Numeric(_)) => Ordering::Greater,
+        }
+    }
+}
+
+impl PartialOrd for Identifier {
+    fn partial_cmp(&self, other: &Identifier) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Identifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Identifier::Numeric(n) => write!(f, "{}", n),
+            Identifier::AlphaNumeric(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+/// Semantic version
+#[derive(Debug, Clone)]
+pub struct Version {
+    major: u64,
+    minor: u64,
+    patch: u64,
+    pre_release: Vec<Identifier>,
+    build: Vec<String>,
+}
+
+impl Version {
+    pub fn major(&self) -> u64 {
+        self.major
+    }
+
+    pub fn minor(&self) -> u64 {
+        self.minor
+    }
+
+    pub fn patch(&self) -> u64 {
+        self.patch
+    }
+
+    /// Pre-release identifiers, empty for the release version
+    pub fn pre_release(&self) -> &[Identifier] {
+        self.pre_release.as_slice()
+    }
+
+    /// Build metadata identifiers
+    pub fn build(&self) -> &[String] {
+        self.build.as_slice()
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Version) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (self.pre_release.is_empty(), other.pre_release.is_empty()) {
+                // Release has higher precedence than any pre-release
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                _ => self.pre_release.cmp(&other.pre_release),
+            })
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Version) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Version) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Version {}
+
+fn is_valid_identifier(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+fn parse_number(s: &str, what: &str) -> Result<u64> {
+    if s.is_empty() || !s.chars().all(|c| c.is_ascii_digit()) {
+        return Err(Error::with_message(format!("invalid {} version", what)).context(what.to_string(), s.to_string()));
+    }
+
+    if s.len() > 1 && s.starts_with('0') {
+        return Err(Error::with_message(format!(
+            "{} version must not have leading zeros",
+            what
+        )));
+    }
+
+    s.parse()
+        .map_err(|_| Error::with_message(format!("{} version is too large", what)))
+}
+
+fn parse_pre_release(s: &str) -> Result<Identifier> {
+    if !is_valid_identifier(s) {
+        return Err(Error::with_message("invalid pre-release identifier").context("identifier", s.to_string()));
+    }
+
+    if s.chars().all(|c| c.is_ascii_digit()) {
+        return Ok(Identifier::Numeric(parse_number(s, "pre-release")?));
+    }
+
+    Ok(Identifier::AlphaNumeric(s.to_string()))
+}
+
+impl FromStr for Version {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Version> {
+        if s.starts_with('v') || s.starts_with('V') {
+            return Err(Error::with_message("version must not start with 'v'"));
+        }
+
+        let (s, build) = match s.find('+') {
+            Some(idx) => (&s[..idx], Some(&s[idx + 1..])),
+            None => (s, None),
+        };
+
+        let (s, pre_release) = match s.find('-') {
+            Some(idx) => (&s[..idx], Some(&s[idx + 1..])),
+            None => (s, None),
+        };
+
+        let core: Vec<&str> = s.split('.').collect();
+        if core.len() != 3 {
+            return Err(Error::with_message("expected '<major>.<minor>.<patch>'").context("version", s.to_string()));
+        }
+
+        let pre_release = match pre_release {
+            Some(x) => x.split('.').map(parse_pre_release).collect::<Result<Vec<_>>>()?,
+            None => vec![],
+        };
+
+        let build = match build {
+            Some(x) => x
+                .split('.')
+                .map(|x| {
+                    if is_valid_identifier(x) {
+                        Ok(x.to_string())
+                    } else {
+                        Err(Error::with_message("invalid build identifier").context("identifier", x.to_string()))
+                    }
+                })
+                .collect::<Result<Vec<_>>>()?,
+            None => vec![],
+        };
+
+        Ok(Version {
+            major: parse_number(core[0], "major")?,
+            minor: parse_number(core[1], "minor")?,
+            patch: parse_number(core[2], "patch")?,
+            pre_release,
+            build,
+        })
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+
+        if !self.pre_release.is_empty() {
+            let ids: Vec<String> = self.pre_release.iter().map(Identifier::to_string).collect();
+            write!(f, "-{}", ids.join("."))?;
+        }
+
+        if !self.build.is_empty() {
+            write!(f, "+{}", self.build.join("."))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Version {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn components() {
+        let version = parse("1.2.3-alpha.1+build.5");
+        assert_eq!((version.major(), version.minor(), version.patch()), (1, 2, 3));
+        assert_eq!(
+            version.pre_release(),
+            &[Identifier::AlphaNumeric("alpha".to_string()), Identifier::Numeric(1)]
+        );
+        assert_eq!(version.build(), &["build".to_string(), "5".to_string()]);
+        assert_eq!(version.to_string(), "1.2.3-alpha.1+build.5");
+
+        // Hyphens are valid in identifiers
+        assert_eq!(parse("1.0.0-x-y-z.-").to_string(), "1.0.0-x-y-z.-");
+        assert_eq!(parse("1.0.0+001").build(), &["001".to_string()]);
+    }
+
+    #[test]
+    fn precedence() {
+        // https://semver.org/spec/v2.0.0.html#spec-item-11
+        let versions = [
+            "1.0.0-alpha",
+            "1.0.0-alpha.1",
+            "1.0.0-alpha.beta",
+            "1.0.0-beta",
+            "1.0.0-beta.2",
+            "1.0.0-beta.11",
+            "1.0.0-rc.1",
+            "1.0.0",
+            "1.0.1",
+            "1.1.0",
+            "2.0.0",
+        ];
+
+        for pair in versions.windows(2) {
+            assert!(parse(pair[0]) < parse(pair[1]), "{} < {}", pair[0], pair[1]);
+        }
+
+        assert_eq!(parse("1.0.0+a"), parse("1.0.0+b"));
+    }
+
+    #[test]
+    fn invalid() {
+        for s in &[
+            "",
+            "1",
+            "1.2",
+            "1.2.3.4",
+            "v1.2.3",
+            "01.2.3",
+            "1.02.3",
+            "1.2.3-",
+            "1.2.3-01",
+            "1.2.3-a..b",
+            "1.2.3+",
+            "1.2.3+a_b",
+            "1.2.x",
+            "-1.2.3",
+            "18446744073709551616.0.0",
+        ] {
+            assert!(s.parse::<Version>().is_err(), "{}", s);
+        }
+    }
+}
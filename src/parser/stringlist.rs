@@ -0,0 +1,204 @@
+//! String list
+//!
+//! `stringlist` values have two representations, an array of strings or a single string with
+//! items separated by the `separator` keyword value (space by default).
+//!
+//! ```text
+//! ["foo", "bar"]     <->     "foo bar"
+//! ```
+//!
+//! Whitespace separators split on any whitespace run and leading / trailing whitespace is
+//! ignored. Other separators split exactly, `"foo,,bar"` contains an empty item.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use reconfix::parser::stringlist::{to_array, to_separated_string, StringList};
+//! use serde_json::json;
+//!
+//! let list = StringList::parse("foo,bar", ",").unwrap();
+//! assert_eq!(list.items(), &["foo".to_string(), "bar".to_string()]);
+//!
+//! assert_eq!(to_array(&json!("foo  bar"), " ").unwrap(), json!(["foo", "bar"]));
+//! assert_eq!(to_separated_string(&json!(["foo", "bar"]), ",").unwrap(), json!("foo,bar"));
+//! ```
+use serde_json::Value;
+
+use crate::error::{Error, Result};
+
+/// Separator used if the `separator` keyword is not set
+pub const DEFAULT_SEPARATOR: &str = " ";
+
+fn is_whitespace_separator(separator: &str) -> bool {
+    separator.trim().is_empty()
+}
+
+fn check_separator(separator: &str) -> Result<()> {
+    if separator.is_empty() {
+        return Err(Error::with_message("separator must not be empty"));
+    }
+    Ok(())
+}
+
+/// Returns `true` if the item survives join & split with the separator
+pub fn is_joinable(item: &str, separator: &str) -> bool {
+    if is_whitespace_separator(separator) {
+        !item.is_empty() && !item.contains(char::is_whitespace)
+    } else {
+        !item.contains(separator)
+    }
+}
+
+/// List of strings
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct StringList(Vec<String>);
+
+impl StringList {
+    /// Splits the string
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - A separated string, an empty string is an empty list
+    /// * `separator` - A non empty separator
+    pub fn parse(s: &str, separator: &str) -> Result<StringList> {
+        check_separator(separator)?;
+
+        let items = if is_whitespace_separator(separator) {
+            s.split_whitespace().map(str::to_string).collect()
+        } else if s.is_empty() {
+            vec![]
+        } else {
+            s.split(separator).map(str::to_string).collect()
+        };
+
+        Ok(StringList(items))
+    }
+
+    /// Creates list from any representation, an array of strings or a separated string
+    pub fn from_value(value: &Value, separator: &str) -> Result<StringList> {
+        match value {
+            Value::String(s) => StringList::parse(s, separator),
+            Value::Array(items) => items
+                .iter()
+                .enumerate()
+                .map(|(idx, x)| {
+                    x.as_str()
+                        .map(str::to_string)
+                        .ok_or_else(|| Error::with_message(format!("item #{} is not a string", idx + 1)))
+                })
+                .collect::<Result<Vec<_>>>()
+                .map(StringList),
+            _ => Err(Error::with_message("expected array of strings or string")),
+        }
+    }
+
+    pub fn items(&self) -> &[String] {
+        self.0.as_slice()
+    }
+
+    pub fn into_items(self) -> Vec<String> {
+        self.0
+    }
+
+    /// Joins items with the separator
+    ///
+    /// Fails if any item contains the separator, it would be split into multiple items, or
+    /// if the joined string doesn't split back to the same items (`["xa", "y"]` joined
+    /// with `"aa"` splits to `["x", "ay"]`).
+    pub fn join(&self, separator: &str) -> Result<String> {
+        check_separator(separator)?;
+
+        if let Some(idx) = self.0.iter().position(|x| !is_joinable(x, separator)) {
+            return Err(
+                Error::with_message(format!("item #{} can't be joined, it contains the separator", idx + 1))
+                    .context("separator", format!("'{}'", separator)),
+            );
+        }
+
+        let joined = self.0.join(separator);
+
+        if StringList::parse(&joined, separator)? != *self {
+            return Err(
+                Error::with_message("items can't be joined, joined string splits into different items")
+                    .context("separator", format!("'{}'", separator)),
+            );
+        }
+
+        Ok(joined)
+    }
+}
+
+impl From<Vec<String>> for StringList {
+    fn from(items: Vec<String>) -> StringList {
+        StringList(items)
+    }
+}
+
+/// Converts `stringlist` value to an array of strings
+pub fn to_array(value: &Value, separator: &str) -> Result<Value> {
+    let list = StringList::from_value(value, separator)?;
+    Ok(Value::Array(list.into_items().into_iter().map(Value::String).collect()))
+}
+
+/// Converts `stringlist` value to a separated string
+pub fn to_separated_string(value: &Value, separator: &str) -> Result<Value> {
+    StringList::from_value(value, separator)?
+        .join(separator)
+        .map(Value::String)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn items(s: &str, separator: &str) -> Vec<String> {
+        StringList::parse(s, separator).unwrap().into_items()
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(items("foo bar", " "), vec!["foo", "bar"]);
+        assert_eq!(items("  foo \t bar\n", " "), vec!["foo", "bar"]);
+        assert_eq!(items("foo,bar", ","), vec!["foo", "bar"]);
+        assert_eq!(items("foo,,bar", ","), vec!["foo", "", "bar"]);
+        assert_eq!(items("foo, bar", ", "), vec!["foo", "bar"]);
+        assert!(items("", ",").is_empty());
+        assert!(items(" ", " ").is_empty());
+        assert!(StringList::parse("foo", "").is_err());
+    }
+
+    #[test]
+    fn join() {
+        let list = StringList::from(vec!["foo".to_string(), "bar".to_string()]);
+        assert_eq!(list.join(" ").unwrap(), "foo bar");
+        assert_eq!(list.join(", ").unwrap(), "foo, bar");
+
+        assert!(StringList::from(vec!["foo bar".to_string()]).join(" ").is_err());
+        assert!(StringList::from(vec!["foo\tbar".to_string()]).join(" ").is_err());
+        assert!(StringList::from(vec!["".to_string()]).join(" ").is_err());
+        assert!(StringList::from(vec!["foo,bar".to_string()]).join(",").is_err());
+        assert!(StringList::from(vec!["foo bar".to_string()]).join(",").is_ok());
+        // Separator formed across the item boundary
+        let list = StringList::from(vec!["xa".to_string(), "y".to_string()]);
+        assert!(list.join("aa").is_err());
+        let list = StringList::from(vec!["x".to_string(), "ay".to_string()]);
+        assert_eq!(list.join("aa").unwrap(), "xaaay");
+    }
+
+    #[test]
+    fn conversions() {
+        assert_eq!(to_array(&json!("foo bar"), " ").unwrap(), json!(["foo", "bar"]));
+        assert_eq!(to_array(&json!(["foo", "bar"]), " ").unwrap(), json!(["foo", "bar"]));
+        assert_eq!(
+            to_separated_string(&json!(["foo", "bar"]), ":").unwrap(),
+            json!("foo:bar")
+        );
+        assert_eq!(to_separated_string(&json!("foo bar"), " ").unwrap(), json!("foo bar"));
+        assert_eq!(to_separated_string(&json!([]), " ").unwrap(), json!(""));
+
+        assert!(to_array(&json!(["foo", 1]), " ").is_err());
+        assert!(to_array(&json!(10), " ").is_err());
+        assert!(to_separated_string(&json!(["foo:bar"]), ":").is_err());
+    }
+}
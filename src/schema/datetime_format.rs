@@ -0,0 +1,60 @@
+use std::{fmt, str::FromStr};
+
+use chrono::format::{Item, StrftimeItems};
+
+use crate::error::Error;
+
+/// strftime-style format of the date & time values (`format` keyword)
+///
+/// Format specifiers are documented in the
+/// [chrono](https://docs.rs/chrono/0.4/chrono/format/strftime/index.html) crate.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DateTimeFormat(String);
+
+impl DateTimeFormat {
+    pub fn items(&self) -> StrftimeItems<'_> {
+        StrftimeItems::new(&self.0)
+    }
+}
+
+impl AsRef<str> for DateTimeFormat {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for DateTimeFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for DateTimeFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(Error::with_message("empty date & time format"));
+        }
+
+        if StrftimeItems::new(s).any(|x| x == Item::Error) {
+            return Err(Error::with_message("invalid date & time format").context("format", s.to_string()));
+        }
+
+        Ok(DateTimeFormat(s.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_str() {
+        assert_eq!("%d/%m/%Y".parse::<DateTimeFormat>().unwrap().as_ref(), "%d/%m/%Y");
+        assert!("%Y-%m-%d %H:%M:%S %z".parse::<DateTimeFormat>().is_ok());
+        assert!("".parse::<DateTimeFormat>().is_err());
+        assert!("%Q".parse::<DateTimeFormat>().is_err());
+        assert!("%Y-%".parse::<DateTimeFormat>().is_err());
+    }
+}
@@ -0,0 +1,80 @@
+use serde::de;
+use serde_yaml::Value;
+
+use crate::parser::cidr::Cidr;
+
+/// `inNetwork` keyword value
+#[derive(Debug, PartialEq)]
+pub enum InNetwork {
+    /// A network in the CIDR notation
+    Cidr(Cidr),
+    /// An expression to evaluate to get a network, usually from another property
+    ///
+    /// The validator evaluates formulas against the validated data, relative identifiers
+    /// (`super.network`) start at the address position.
+    Formula(String),
+}
+
+impl InNetwork {
+    pub fn cidr(&self) -> Option<&Cidr> {
+        match self {
+            InNetwork::Cidr(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn formula(&self) -> Option<&str> {
+        match self {
+            InNetwork::Formula(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+impl<'de> de::Deserialize<'de> for InNetwork {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let value = Value::deserialize(deserializer)?;
+        match value {
+            Value::Mapping(ref mapping) => {
+                let eval_key = Value::String("formula".to_string());
+                match mapping.get(&eval_key) {
+                    Some(Value::String(s)) => Ok(InNetwork::Formula(s.to_string())),
+                    Some(_) => Err(de::Error::custom("mapping contains formula, but it's not a string")),
+                    None => Err(de::Error::custom("inNetwork must be a string")),
+                }
+            }
+            Value::String(s) => Ok(InNetwork::Cidr(
+                s.parse::<Cidr>().map_err(|e| de::Error::custom(e.message()))?,
+            )),
+            _ => Err(de::Error::custom("inNetwork must be a string")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cidr() {
+        let n: InNetwork = serde_yaml::from_str("10.0.0.0/8").unwrap();
+        assert_eq!(n.cidr().map(|x| x.to_string()), Some("10.0.0.0/8".to_string()));
+    }
+
+    #[test]
+    fn formula() {
+        let n: InNetwork = serde_yaml::from_str("formula: expr").unwrap();
+        assert_eq!(n.formula(), Some("expr"));
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(serde_yaml::from_str::<InNetwork>("10.0.0.1/8").is_err());
+        assert!(serde_yaml::from_str::<InNetwork>("foo: bar").is_err());
+        assert!(serde_yaml::from_str::<InNetwork>("formula: 10").is_err());
+        assert!(serde_yaml::from_str::<InNetwork>("10").is_err());
+    }
+}
@@ -0,0 +1,53 @@
+use std::{fmt, str::FromStr};
+
+use crate::error::Error;
+
+const IPV4_KEYWORD: &str = "ipv4";
+const IPV6_KEYWORD: &str = "ipv6";
+
+/// IP address family (`family` keyword)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IpFamily {
+    IPv4,
+    IPv6,
+}
+
+impl AsRef<str> for IpFamily {
+    fn as_ref(&self) -> &str {
+        match self {
+            IpFamily::IPv4 => IPV4_KEYWORD,
+            IpFamily::IPv6 => IPV6_KEYWORD,
+        }
+    }
+}
+
+impl fmt::Display for IpFamily {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_ref())
+    }
+}
+
+impl FromStr for IpFamily {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            IPV4_KEYWORD => Ok(IpFamily::IPv4),
+            IPV6_KEYWORD => Ok(IpFamily::IPv6),
+            _ => Err(Error::with_message("invalid IP family, expected 'ipv4' or 'ipv6'")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_str() {
+        assert_eq!("ipv4".parse::<IpFamily>().unwrap(), IpFamily::IPv4);
+        assert_eq!("ipv6".parse::<IpFamily>().unwrap(), IpFamily::IPv6);
+        assert!("IPv4".parse::<IpFamily>().is_err());
+        assert!("inet".parse::<IpFamily>().is_err());
+    }
+}
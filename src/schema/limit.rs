@@ -0,0 +1,54 @@
+use serde_derive::Deserialize;
+use serde_yaml::Number;
+
+/// `min` & `max` keywords value
+///
+/// Numeric types use numbers only. Types with their own units (`duration`, `bytesize`,
+/// `semver`) accept strings in their natural units (`30s`, `512MiB`, `1.2.0`) too.
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum Limit {
+    Number(Number),
+    String(String),
+}
+
+impl Limit {
+    pub fn as_number(&self) -> Option<&Number> {
+        match self {
+            Limit::Number(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Limit::String(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn number() {
+        let l: Limit = serde_yaml::from_str("10").unwrap();
+        assert_eq!(l.as_number(), Some(&Number::from(10)));
+        assert_eq!(l.as_str(), None);
+
+        let l: Limit = serde_yaml::from_str("1.5").unwrap();
+        assert_eq!(l.as_number().and_then(Number::as_f64), Some(1.5));
+    }
+
+    #[test]
+    fn string() {
+        let l: Limit = serde_yaml::from_str("512MiB").unwrap();
+        assert_eq!(l.as_str(), Some("512MiB"));
+        assert_eq!(l.as_number(), None);
+
+        let l: Limit = serde_yaml::from_str("1.2.0").unwrap();
+        assert_eq!(l.as_str(), Some("1.2.0"));
+    }
+}
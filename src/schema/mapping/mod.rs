@@ -12,7 +12,7 @@ use crate::utils::deref::OptionDeref;
 
 pub use self::{
     filename::FileName,
-    target::{LocationPartition, RawTarget, Target, TargetFormat, TargetLocation, TargetType},
+    target::{LocationPartition, RawTarget, Target, TargetBlock, TargetFormat, TargetLocation, TargetType},
 };
 
 mod filename;
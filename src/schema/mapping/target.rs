@@ -48,6 +48,30 @@ pub enum TargetFormat {
     Text,
     #[serde(rename = "redsocks")]
     Redsocks,
+    /// Raspberry Pi `config.txt`
+    #[serde(rename = "config-txt")]
+    ConfigTxt,
+    /// U-Boot environment, text (`uEnv.txt`) or binary
+    #[serde(rename = "uboot-env")]
+    UBootEnv,
+    /// GRUB environment block (`grubenv`)
+    #[serde(rename = "grub-env")]
+    GrubEnv,
+    /// Environment file with `KEY=VALUE` lines (`/etc/default/*`)
+    #[serde(rename = "env-file")]
+    EnvFile,
+    /// YAML document, changed scalars are edited in place
+    #[serde(rename = "yaml")]
+    Yaml,
+    /// TOML document, edited in place
+    #[serde(rename = "toml")]
+    Toml,
+    /// `/etc/hosts`
+    #[serde(rename = "hosts")]
+    Hosts,
+    /// SSH `authorized_keys`
+    #[serde(rename = "authorized-keys")]
+    AuthorizedKeys,
 }
 
 impl TargetFormat {
@@ -85,6 +109,62 @@ impl TargetFormat {
             _ => false,
         }
     }
+
+    pub fn is_config_txt(self) -> bool {
+        match self {
+            TargetFormat::ConfigTxt => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_uboot_env(self) -> bool {
+        match self {
+            TargetFormat::UBootEnv => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_grub_env(self) -> bool {
+        match self {
+            TargetFormat::GrubEnv => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_env_file(self) -> bool {
+        match self {
+            TargetFormat::EnvFile => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_yaml(self) -> bool {
+        match self {
+            TargetFormat::Yaml => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_toml(self) -> bool {
+        match self {
+            TargetFormat::Toml => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_hosts(self) -> bool {
+        match self {
+            TargetFormat::Hosts => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_authorized_keys(self) -> bool {
+        match self {
+            TargetFormat::AuthorizedKeys => true,
+            _ => false,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -133,6 +213,28 @@ impl TargetLocation {
     }
 }
 
+/// Binary block layout of the target file
+///
+/// Used by the `uboot-env` format, the environment is a plain text (`uEnv.txt`) if not set.
+#[derive(Debug, Copy, Clone, PartialEq, Deserialize)]
+pub struct TargetBlock {
+    /// Whole block size including header (`CONFIG_ENV_SIZE`)
+    size: usize,
+    /// Redundant block (`CONFIG_SYS_REDUNDAND_ENVIRONMENT`)
+    #[serde(default)]
+    redundant: bool,
+}
+
+impl TargetBlock {
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_redundant(&self) -> bool {
+        self.redundant
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct RawTarget {
     #[serde(rename = "type")]
@@ -140,6 +242,7 @@ pub struct RawTarget {
     format: TargetFormat,
     glob: Option<String>,
     location: TargetLocation,
+    block: Option<TargetBlock>,
 }
 
 impl RawTarget {
@@ -158,6 +261,10 @@ impl RawTarget {
     pub fn location(&self) -> &TargetLocation {
         &self.location
     }
+
+    pub fn block(&self) -> Option<&TargetBlock> {
+        self.block.as_ref()
+    }
 }
 
 #[derive(Debug, PartialEq, Deserialize)]
@@ -268,6 +375,24 @@ mod tests {
         assert_eq!(t.location().path(), "/config.json");
     }
 
+    #[test]
+    fn raw_target_block() {
+        let schema = r#"
+            type: file
+            format: uboot-env
+            location:
+                partition: 0
+                path: /uboot.env
+            block:
+                size: 16384
+                redundant: true
+        "#;
+        let t: RawTarget = serde_yaml::from_str(schema).unwrap();
+        let block = t.block().unwrap();
+        assert_eq!(block.size(), 16384);
+        assert!(block.is_redundant());
+    }
+
     #[test]
     fn format_ini() {
         let f: TargetFormat = serde_yaml::from_str("ini").unwrap();
@@ -286,6 +411,54 @@ mod tests {
         assert!(f.is_binary());
     }
 
+    #[test]
+    fn format_config_txt() {
+        let f: TargetFormat = serde_yaml::from_str("config-txt").unwrap();
+        assert!(f.is_config_txt());
+    }
+
+    #[test]
+    fn format_uboot_env() {
+        let f: TargetFormat = serde_yaml::from_str("uboot-env").unwrap();
+        assert!(f.is_uboot_env());
+    }
+
+    #[test]
+    fn format_grub_env() {
+        let f: TargetFormat = serde_yaml::from_str("grub-env").unwrap();
+        assert!(f.is_grub_env());
+    }
+
+    #[test]
+    fn format_env_file() {
+        let f: TargetFormat = serde_yaml::from_str("env-file").unwrap();
+        assert!(f.is_env_file());
+    }
+
+    #[test]
+    fn format_yaml() {
+        let f: TargetFormat = serde_yaml::from_str("yaml").unwrap();
+        assert!(f.is_yaml());
+    }
+
+    #[test]
+    fn format_toml() {
+        let f: TargetFormat = serde_yaml::from_str("toml").unwrap();
+        assert!(f.is_toml());
+    }
+
+    #[test]
+    fn format_hosts() {
+        let f: TargetFormat = serde_yaml::from_str("hosts").unwrap();
+        assert!(f.is_hosts());
+    }
+
+    #[test]
+    fn format_authorized_keys() {
+        let f: TargetFormat = serde_yaml::from_str("authorized-keys").unwrap();
+        assert!(f.is_authorized_keys());
+    }
+
     #[test]
     fn type_file() {
         let t: TargetType = serde_yaml::from_str("file").unwrap();
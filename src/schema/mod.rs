@@ -8,6 +8,10 @@ pub use serde_yaml::{Number, Value};
 
 // Reexport everything except mapping, which is a public module
 pub use self::{
+    datetime_format::DateTimeFormat,
+    in_network::InNetwork,
+    ip_family::IpFamily,
+    limit::Limit,
     property::Property,
     r#enum::EnumEntry,
     r#type::{PrimitiveType, Type},
@@ -20,7 +24,11 @@ use crate::{
     utils::deref::OptionDeref,
 };
 
+mod datetime_format;
 mod r#enum;
+mod in_network;
+mod ip_family;
+mod limit;
 pub mod mapping;
 mod property;
 mod r#type;
@@ -97,13 +105,15 @@ pub struct Schema {
     #[serde(default, rename = "multipleOf", skip_serializing_if = "Option::is_none")]
     multiple_of: Option<Number>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    max: Option<Number>,
+    max: Option<Limit>,
     #[serde(default, rename = "exclusiveMax", skip_serializing_if = "Option::is_none")]
     exclusive_max: Option<Number>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    min: Option<Number>,
+    min: Option<Limit>,
     #[serde(default, rename = "exclusiveMin", skip_serializing_if = "Option::is_none")]
     exclusive_min: Option<Number>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    step: Option<Number>,
     //
     // String based types validation keywords
     //
@@ -117,6 +127,138 @@ pub struct Schema {
         deserialize_with = "deserialize_option_from_str"
     )]
     pattern: Option<Regex>,
+    //
+    // Content validation keywords
+    //
+    #[serde(default, rename = "contentEncoding", skip_serializing_if = "Option::is_none")]
+    content_encoding: Option<String>,
+    #[serde(default, rename = "contentMediaType", skip_serializing_if = "Option::is_none")]
+    content_media_type: Option<String>,
+    #[serde(default, rename = "contentSchema", skip_serializing_if = "Option::is_none")]
+    content_schema: Option<Box<Schema>>,
+    //
+    // File validation keywords
+    //
+    #[serde(default, rename = "mediaTypes", skip_serializing_if = "Vec::is_empty")]
+    media_types: Vec<String>,
+    #[serde(default, rename = "maxSize", skip_serializing_if = "Option::is_none")]
+    max_size: Option<usize>,
+    #[serde(default, rename = "minSize", skip_serializing_if = "Option::is_none")]
+    min_size: Option<usize>,
+    #[serde(
+        default,
+        rename = "namePattern",
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_option_from_str"
+    )]
+    name_pattern: Option<Regex>,
+    //
+    // Image (file) validation keywords
+    //
+    #[serde(default, rename = "imageFormat", skip_serializing_if = "Option::is_none")]
+    image_format: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    width: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    height: Option<u32>,
+    #[serde(default, rename = "maxWidth", skip_serializing_if = "Option::is_none")]
+    max_width: Option<u32>,
+    #[serde(default, rename = "maxHeight", skip_serializing_if = "Option::is_none")]
+    max_height: Option<u32>,
+    #[serde(default, rename = "minWidth", skip_serializing_if = "Option::is_none")]
+    min_width: Option<u32>,
+    #[serde(default, rename = "minHeight", skip_serializing_if = "Option::is_none")]
+    min_height: Option<u32>,
+    //
+    // Certificate validation keywords
+    //
+    #[serde(default, rename = "notExpiredAt", skip_serializing_if = "Option::is_none")]
+    not_expired_at: Option<Limit>,
+    #[serde(default, rename = "isCA", skip_serializing_if = "Option::is_none")]
+    is_ca: Option<bool>,
+    //
+    // Network validation keywords
+    //
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_option_from_str"
+    )]
+    family: Option<IpFamily>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    private: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    multicast: Option<bool>,
+    //
+    // IP address validation keywords
+    //
+    #[serde(default, rename = "inNetwork", skip_serializing_if = "Option::is_none")]
+    in_network: Option<InNetwork>,
+    #[serde(default, rename = "notReserved")]
+    not_reserved: bool,
+    #[serde(default, rename = "privateOnly")]
+    private_only: bool,
+    #[serde(default, rename = "unicastOnly")]
+    unicast_only: bool,
+    //
+    // UUID validation keywords
+    //
+    #[serde(default, rename = "uuidVersion", skip_serializing_if = "Option::is_none")]
+    uuid_version: Option<u8>,
+    //
+    // Hex validation keywords
+    //
+    #[serde(default, rename = "maxBytes", skip_serializing_if = "Option::is_none")]
+    max_bytes: Option<usize>,
+    #[serde(default, rename = "minBytes", skip_serializing_if = "Option::is_none")]
+    min_bytes: Option<usize>,
+    //
+    // Date & time validation keywords
+    //
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_option_from_str"
+    )]
+    format: Option<DateTimeFormat>,
+    #[serde(default, rename = "timezoneRequired")]
+    timezone_required: bool,
+    //
+    // Password validation keywords
+    //
+    #[serde(default, rename = "minLowercase", skip_serializing_if = "Option::is_none")]
+    min_lowercase: Option<usize>,
+    #[serde(default, rename = "minUppercase", skip_serializing_if = "Option::is_none")]
+    min_uppercase: Option<usize>,
+    #[serde(default, rename = "minDigits", skip_serializing_if = "Option::is_none")]
+    min_digits: Option<usize>,
+    #[serde(default, rename = "minSymbols", skip_serializing_if = "Option::is_none")]
+    min_symbols: Option<usize>,
+    #[serde(default, rename = "minEntropy", skip_serializing_if = "Option::is_none")]
+    min_entropy: Option<f64>,
+    #[serde(default, rename = "noDictionaryWords")]
+    no_dictionary_words: bool,
+    //
+    // Text validation keywords
+    //
+    #[serde(default, rename = "maxLines", skip_serializing_if = "Option::is_none")]
+    max_lines: Option<usize>,
+    #[serde(default, rename = "minLines", skip_serializing_if = "Option::is_none")]
+    min_lines: Option<usize>,
+    #[serde(
+        default,
+        rename = "linePattern",
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_option_from_str"
+    )]
+    line_pattern: Option<Regex>,
+    #[serde(
+        default,
+        rename = "forbidPattern",
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_option_from_str"
+    )]
+    forbid_pattern: Option<Regex>,
 }
 
 impl Schema {
@@ -219,10 +361,20 @@ impl Schema {
     }
 
     pub fn max(&self) -> Option<&Number> {
-        self.max.as_ref()
+        self.max.as_ref().and_then(Limit::as_number)
     }
 
     pub fn min(&self) -> Option<&Number> {
+        self.min.as_ref().and_then(Limit::as_number)
+    }
+
+    /// `max` keyword value, including values in the type units (`512MiB`)
+    pub fn max_limit(&self) -> Option<&Limit> {
+        self.max.as_ref()
+    }
+
+    /// `min` keyword value, including values in the type units (`30s`)
+    pub fn min_limit(&self) -> Option<&Limit> {
         self.min.as_ref()
     }
 
@@ -233,6 +385,11 @@ impl Schema {
     pub fn exclusive_min(&self) -> Option<&Number> {
         self.exclusive_min.as_ref()
     }
+
+    /// `step` keyword value, steps are counted from `min` (or zero if not set)
+    pub fn step(&self) -> Option<&Number> {
+        self.step.as_ref()
+    }
 }
 
 //
@@ -252,6 +409,217 @@ impl Schema {
     }
 }
 
+//
+// Content validation keywords
+//
+impl Schema {
+    pub fn content_encoding(&self) -> Option<&str> {
+        self.content_encoding.as_deref()
+    }
+
+    pub fn content_media_type(&self) -> Option<&str> {
+        self.content_media_type.as_deref()
+    }
+
+    pub fn content_schema(&self) -> Option<&Schema> {
+        self.content_schema.as_deref()
+    }
+}
+
+//
+// File validation keywords
+//
+impl Schema {
+    pub fn media_types(&self) -> &[String] {
+        self.media_types.as_slice()
+    }
+
+    pub fn max_size(&self) -> Option<usize> {
+        self.max_size
+    }
+
+    pub fn min_size(&self) -> Option<usize> {
+        self.min_size
+    }
+
+    pub fn name_pattern(&self) -> Option<&Regex> {
+        self.name_pattern.as_ref()
+    }
+}
+
+//
+// Image (file) validation keywords
+//
+impl Schema {
+    pub fn image_format(&self) -> Option<&str> {
+        self.image_format.as_deref()
+    }
+
+    pub fn width(&self) -> Option<u32> {
+        self.width
+    }
+
+    pub fn height(&self) -> Option<u32> {
+        self.height
+    }
+
+    pub fn max_width(&self) -> Option<u32> {
+        self.max_width
+    }
+
+    pub fn max_height(&self) -> Option<u32> {
+        self.max_height
+    }
+
+    pub fn min_width(&self) -> Option<u32> {
+        self.min_width
+    }
+
+    pub fn min_height(&self) -> Option<u32> {
+        self.min_height
+    }
+}
+
+//
+// Certificate validation keywords
+//
+impl Schema {
+    pub fn not_expired_at(&self) -> Option<&Limit> {
+        self.not_expired_at.as_ref()
+    }
+
+    pub fn is_ca(&self) -> Option<bool> {
+        self.is_ca
+    }
+}
+
+//
+// Network validation keywords
+//
+impl Schema {
+    pub fn family(&self) -> Option<IpFamily> {
+        self.family
+    }
+
+    pub fn private(&self) -> Option<bool> {
+        self.private
+    }
+
+    pub fn multicast(&self) -> Option<bool> {
+        self.multicast
+    }
+}
+
+//
+// IP address validation keywords
+//
+impl Schema {
+    pub fn in_network(&self) -> Option<&InNetwork> {
+        self.in_network.as_ref()
+    }
+
+    pub fn not_reserved(&self) -> bool {
+        self.not_reserved
+    }
+
+    pub fn private_only(&self) -> bool {
+        self.private_only
+    }
+
+    pub fn unicast_only(&self) -> bool {
+        self.unicast_only
+    }
+}
+
+//
+// UUID validation keywords
+//
+impl Schema {
+    pub fn uuid_version(&self) -> Option<u8> {
+        self.uuid_version
+    }
+}
+
+//
+// Hex validation keywords
+//
+impl Schema {
+    pub fn max_bytes(&self) -> Option<usize> {
+        self.max_bytes
+    }
+
+    pub fn min_bytes(&self) -> Option<usize> {
+        self.min_bytes
+    }
+}
+
+//
+// Date & time validation keywords
+//
+impl Schema {
+    /// strftime-style format of the legacy values, RFC 3339 is used if not set
+    pub fn format(&self) -> Option<&DateTimeFormat> {
+        self.format.as_ref()
+    }
+
+    pub fn timezone_required(&self) -> bool {
+        self.timezone_required
+    }
+}
+
+//
+// Password validation keywords
+//
+impl Schema {
+    pub fn min_lowercase(&self) -> Option<usize> {
+        self.min_lowercase
+    }
+
+    pub fn min_uppercase(&self) -> Option<usize> {
+        self.min_uppercase
+    }
+
+    pub fn min_digits(&self) -> Option<usize> {
+        self.min_digits
+    }
+
+    pub fn min_symbols(&self) -> Option<usize> {
+        self.min_symbols
+    }
+
+    /// Minimum estimated entropy in bits
+    pub fn min_entropy(&self) -> Option<f64> {
+        self.min_entropy
+    }
+
+    pub fn no_dictionary_words(&self) -> bool {
+        self.no_dictionary_words
+    }
+}
+
+//
+// Text validation keywords
+//
+impl Schema {
+    pub fn max_lines(&self) -> Option<usize> {
+        self.max_lines
+    }
+
+    pub fn min_lines(&self) -> Option<usize> {
+        self.min_lines
+    }
+
+    /// Pattern every line must match
+    pub fn line_pattern(&self) -> Option<&Regex> {
+        self.line_pattern.as_ref()
+    }
+
+    /// Pattern no line can match
+    pub fn forbid_pattern(&self) -> Option<&Regex> {
+        self.forbid_pattern.as_ref()
+    }
+}
+
 //
 // Array validation keywords
 //
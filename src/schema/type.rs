@@ -13,6 +13,8 @@ const NUMBER_KEYWORD: &str = "number";
 const DATE_TIME_KEYWORD: &str = "datetime";
 const DATE_KEYWORD: &str = "date";
 const TIME_KEYWORD: &str = "time";
+const TIMEZONE_KEYWORD: &str = "timezone"; // TODO: Update spec
+const LOCALE_KEYWORD: &str = "locale"; // TODO: Update spec
 const EMAIL_KEYWORD: &str = "email";
 const IPV4_KEYWORD: &str = "ipv4";
 const IPV6_KEYWORD: &str = "ipv6";
@@ -24,6 +26,29 @@ const STRINGLIST_KEYWORD: &str = "stringlist"; // TODO: Update spec
 const DNSMASQ_ADDRESS_KEYWORD: &str = "dnsmasq-address"; // TODO: Update spec
 const CHRONY_ADDRESS_KEYWORD: &str = "chrony-address"; // TODO: Update spec
 const IPTABLES_ADDRESS_KEYWORD: &str = "iptables-address"; // TODO: Update spec
+const HOST_ENTRY_KEYWORD: &str = "host-entry"; // TODO: Update spec
+const SSH_PUBLIC_KEY_KEYWORD: &str = "ssh-public-key"; // TODO: Update spec
+const CERTIFICATE_KEYWORD: &str = "certificate"; // TODO: Update spec
+const MAC_ADDRESS_KEYWORD: &str = "mac-address"; // TODO: Update spec
+const CIDR_KEYWORD: &str = "cidr"; // TODO: Update spec
+const INTERFACE_NAME_KEYWORD: &str = "interface-name"; // TODO: Update spec
+const WIFI_SSID_KEYWORD: &str = "wifi-ssid"; // TODO: Update spec
+const WIFI_PSK_KEYWORD: &str = "wifi-psk"; // TODO: Update spec
+const WIFI_COUNTRY_KEYWORD: &str = "wifi-country"; // TODO: Update spec
+const DURATION_KEYWORD: &str = "duration"; // TODO: Update spec
+const BYTESIZE_KEYWORD: &str = "bytesize"; // TODO: Update spec
+const SEMVER_KEYWORD: &str = "semver"; // TODO: Update spec
+const UUID_KEYWORD: &str = "uuid"; // TODO: Update spec
+const REGEX_KEYWORD: &str = "regex"; // TODO: Update spec
+const HEX_KEYWORD: &str = "hex"; // TODO: Update spec
+const INT8_KEYWORD: &str = "int8"; // TODO: Update spec
+const INT16_KEYWORD: &str = "int16"; // TODO: Update spec
+const INT32_KEYWORD: &str = "int32"; // TODO: Update spec
+const INT64_KEYWORD: &str = "int64"; // TODO: Update spec
+const UINT8_KEYWORD: &str = "uint8"; // TODO: Update spec
+const UINT16_KEYWORD: &str = "uint16"; // TODO: Update spec
+const UINT32_KEYWORD: &str = "uint32"; // TODO: Update spec
+const UINT64_KEYWORD: &str = "uint64"; // TODO: Update spec
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum PrimitiveType {
@@ -38,6 +63,8 @@ pub enum PrimitiveType {
     DateTime,
     Date,
     Time,
+    Timezone,
+    Locale,
     Email,
     IPv4,
     IPv6,
@@ -49,6 +76,29 @@ pub enum PrimitiveType {
     DNSMasqAddress,
     ChronyAddress,
     IPTablesAddress,
+    HostEntry,
+    SshPublicKey,
+    Certificate,
+    MacAddress,
+    Cidr,
+    InterfaceName,
+    WifiSsid,
+    WifiPsk,
+    WifiCountry,
+    Duration,
+    ByteSize,
+    Semver,
+    Uuid,
+    Regex,
+    Hex,
+    Int8,
+    Int16,
+    Int32,
+    Int64,
+    UInt8,
+    UInt16,
+    UInt32,
+    UInt64,
 }
 
 impl AsRef<str> for PrimitiveType {
@@ -65,6 +115,8 @@ impl AsRef<str> for PrimitiveType {
             PrimitiveType::DateTime => DATE_TIME_KEYWORD,
             PrimitiveType::Date => DATE_KEYWORD,
             PrimitiveType::Time => TIME_KEYWORD,
+            PrimitiveType::Timezone => TIMEZONE_KEYWORD,
+            PrimitiveType::Locale => LOCALE_KEYWORD,
             PrimitiveType::Email => EMAIL_KEYWORD,
             PrimitiveType::IPv4 => IPV4_KEYWORD,
             PrimitiveType::IPv6 => IPV6_KEYWORD,
@@ -76,6 +128,29 @@ impl AsRef<str> for PrimitiveType {
             PrimitiveType::DNSMasqAddress => DNSMASQ_ADDRESS_KEYWORD,
             PrimitiveType::ChronyAddress => CHRONY_ADDRESS_KEYWORD,
             PrimitiveType::IPTablesAddress => IPTABLES_ADDRESS_KEYWORD,
+            PrimitiveType::HostEntry => HOST_ENTRY_KEYWORD,
+            PrimitiveType::SshPublicKey => SSH_PUBLIC_KEY_KEYWORD,
+            PrimitiveType::Certificate => CERTIFICATE_KEYWORD,
+            PrimitiveType::MacAddress => MAC_ADDRESS_KEYWORD,
+            PrimitiveType::Cidr => CIDR_KEYWORD,
+            PrimitiveType::InterfaceName => INTERFACE_NAME_KEYWORD,
+            PrimitiveType::WifiSsid => WIFI_SSID_KEYWORD,
+            PrimitiveType::WifiPsk => WIFI_PSK_KEYWORD,
+            PrimitiveType::WifiCountry => WIFI_COUNTRY_KEYWORD,
+            PrimitiveType::Duration => DURATION_KEYWORD,
+            PrimitiveType::ByteSize => BYTESIZE_KEYWORD,
+            PrimitiveType::Semver => SEMVER_KEYWORD,
+            PrimitiveType::Uuid => UUID_KEYWORD,
+            PrimitiveType::Regex => REGEX_KEYWORD,
+            PrimitiveType::Hex => HEX_KEYWORD,
+            PrimitiveType::Int8 => INT8_KEYWORD,
+            PrimitiveType::Int16 => INT16_KEYWORD,
+            PrimitiveType::Int32 => INT32_KEYWORD,
+            PrimitiveType::Int64 => INT64_KEYWORD,
+            PrimitiveType::UInt8 => UINT8_KEYWORD,
+            PrimitiveType::UInt16 => UINT16_KEYWORD,
+            PrimitiveType::UInt32 => UINT32_KEYWORD,
+            PrimitiveType::UInt64 => UINT64_KEYWORD,
         }
     }
 }
@@ -102,6 +177,8 @@ impl FromStr for PrimitiveType {
             DATE_TIME_KEYWORD => Ok(PrimitiveType::DateTime),
             DATE_KEYWORD => Ok(PrimitiveType::Date),
             TIME_KEYWORD => Ok(PrimitiveType::Time),
+            TIMEZONE_KEYWORD => Ok(PrimitiveType::Timezone),
+            LOCALE_KEYWORD => Ok(PrimitiveType::Locale),
             EMAIL_KEYWORD => Ok(PrimitiveType::Email),
             IPV4_KEYWORD => Ok(PrimitiveType::IPv4),
             IPV6_KEYWORD => Ok(PrimitiveType::IPv6),
@@ -113,6 +190,29 @@ impl FromStr for PrimitiveType {
             DNSMASQ_ADDRESS_KEYWORD => Ok(PrimitiveType::DNSMasqAddress),
             CHRONY_ADDRESS_KEYWORD => Ok(PrimitiveType::ChronyAddress),
             IPTABLES_ADDRESS_KEYWORD => Ok(PrimitiveType::IPTablesAddress),
+            HOST_ENTRY_KEYWORD => Ok(PrimitiveType::HostEntry),
+            SSH_PUBLIC_KEY_KEYWORD => Ok(PrimitiveType::SshPublicKey),
+            CERTIFICATE_KEYWORD => Ok(PrimitiveType::Certificate),
+            MAC_ADDRESS_KEYWORD => Ok(PrimitiveType::MacAddress),
+            CIDR_KEYWORD => Ok(PrimitiveType::Cidr),
+            INTERFACE_NAME_KEYWORD => Ok(PrimitiveType::InterfaceName),
+            WIFI_SSID_KEYWORD => Ok(PrimitiveType::WifiSsid),
+            WIFI_PSK_KEYWORD => Ok(PrimitiveType::WifiPsk),
+            WIFI_COUNTRY_KEYWORD => Ok(PrimitiveType::WifiCountry),
+            DURATION_KEYWORD => Ok(PrimitiveType::Duration),
+            BYTESIZE_KEYWORD => Ok(PrimitiveType::ByteSize),
+            SEMVER_KEYWORD => Ok(PrimitiveType::Semver),
+            UUID_KEYWORD => Ok(PrimitiveType::Uuid),
+            REGEX_KEYWORD => Ok(PrimitiveType::Regex),
+            HEX_KEYWORD => Ok(PrimitiveType::Hex),
+            INT8_KEYWORD => Ok(PrimitiveType::Int8),
+            INT16_KEYWORD => Ok(PrimitiveType::Int16),
+            INT32_KEYWORD => Ok(PrimitiveType::Int32),
+            INT64_KEYWORD => Ok(PrimitiveType::Int64),
+            UINT8_KEYWORD => Ok(PrimitiveType::UInt8),
+            UINT16_KEYWORD => Ok(PrimitiveType::UInt16),
+            UINT32_KEYWORD => Ok(PrimitiveType::UInt32),
+            UINT64_KEYWORD => Ok(PrimitiveType::UInt64),
             _ => Err(Error::with_message("invalid primitive object type")),
         }
     }
@@ -208,6 +308,8 @@ mod tests {
         assert_eq!("datetime".parse::<PrimitiveType>().unwrap(), PrimitiveType::DateTime);
         assert_eq!("date".parse::<PrimitiveType>().unwrap(), PrimitiveType::Date);
         assert_eq!("time".parse::<PrimitiveType>().unwrap(), PrimitiveType::Time);
+        assert_eq!("timezone".parse::<PrimitiveType>().unwrap(), PrimitiveType::Timezone);
+        assert_eq!("locale".parse::<PrimitiveType>().unwrap(), PrimitiveType::Locale);
         assert_eq!("email".parse::<PrimitiveType>().unwrap(), PrimitiveType::Email);
         assert_eq!("ipv4".parse::<PrimitiveType>().unwrap(), PrimitiveType::IPv4);
         assert_eq!("ipv6".parse::<PrimitiveType>().unwrap(), PrimitiveType::IPv6);
@@ -231,6 +333,44 @@ mod tests {
             "iptables-address".parse::<PrimitiveType>().unwrap(),
             PrimitiveType::IPTablesAddress
         );
+        assert_eq!("host-entry".parse::<PrimitiveType>().unwrap(), PrimitiveType::HostEntry);
+        assert_eq!(
+            "ssh-public-key".parse::<PrimitiveType>().unwrap(),
+            PrimitiveType::SshPublicKey
+        );
+        assert_eq!(
+            "certificate".parse::<PrimitiveType>().unwrap(),
+            PrimitiveType::Certificate
+        );
+        assert_eq!(
+            "mac-address".parse::<PrimitiveType>().unwrap(),
+            PrimitiveType::MacAddress
+        );
+        assert_eq!("cidr".parse::<PrimitiveType>().unwrap(), PrimitiveType::Cidr);
+        assert_eq!(
+            "interface-name".parse::<PrimitiveType>().unwrap(),
+            PrimitiveType::InterfaceName
+        );
+        assert_eq!("wifi-ssid".parse::<PrimitiveType>().unwrap(), PrimitiveType::WifiSsid);
+        assert_eq!("wifi-psk".parse::<PrimitiveType>().unwrap(), PrimitiveType::WifiPsk);
+        assert_eq!(
+            "wifi-country".parse::<PrimitiveType>().unwrap(),
+            PrimitiveType::WifiCountry
+        );
+        assert_eq!("duration".parse::<PrimitiveType>().unwrap(), PrimitiveType::Duration);
+        assert_eq!("bytesize".parse::<PrimitiveType>().unwrap(), PrimitiveType::ByteSize);
+        assert_eq!("semver".parse::<PrimitiveType>().unwrap(), PrimitiveType::Semver);
+        assert_eq!("uuid".parse::<PrimitiveType>().unwrap(), PrimitiveType::Uuid);
+        assert_eq!("regex".parse::<PrimitiveType>().unwrap(), PrimitiveType::Regex);
+        assert_eq!("hex".parse::<PrimitiveType>().unwrap(), PrimitiveType::Hex);
+        assert_eq!("int8".parse::<PrimitiveType>().unwrap(), PrimitiveType::Int8);
+        assert_eq!("int16".parse::<PrimitiveType>().unwrap(), PrimitiveType::Int16);
+        assert_eq!("int32".parse::<PrimitiveType>().unwrap(), PrimitiveType::Int32);
+        assert_eq!("int64".parse::<PrimitiveType>().unwrap(), PrimitiveType::Int64);
+        assert_eq!("uint8".parse::<PrimitiveType>().unwrap(), PrimitiveType::UInt8);
+        assert_eq!("uint16".parse::<PrimitiveType>().unwrap(), PrimitiveType::UInt16);
+        assert_eq!("uint32".parse::<PrimitiveType>().unwrap(), PrimitiveType::UInt32);
+        assert_eq!("uint64".parse::<PrimitiveType>().unwrap(), PrimitiveType::UInt64);
     }
 
     #[test]
@@ -0,0 +1,62 @@
+//! Common passwords & words
+//!
+//! Words are lowercase and at least 4 characters long, shorter words would match almost
+//! any password.
+const DICTIONARY: &str = include_str!("dictionary.txt");
+
+/// Returns all words in the alphabetical order
+pub fn words() -> impl Iterator<Item = &'static str> {
+    super::entries(DICTIONARY)
+}
+
+/// Undoes common character substitutions (`p@ssw0rd` -> `password`)
+fn normalize(s: &str) -> String {
+    s.chars()
+        .map(|c| match c {
+            '0' => 'o',
+            '1' | '!' => 'i',
+            '3' => 'e',
+            '4' | '@' => 'a',
+            '5' | '$' => 's',
+            '7' => 't',
+            c => c,
+        })
+        .collect()
+}
+
+/// Returns `true` if the value contains any word
+///
+/// The check is case insensitive and common character substitutions (`p@ssw0rd`) are undone.
+pub fn contains_word(s: &str) -> bool {
+    let lowercase = s.to_lowercase();
+    let normalized = normalize(&lowercase);
+
+    words().any(|x| lowercase.contains(x) || normalized.contains(x))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contains() {
+        for s in &["password", "MyPassword!", "P@ssw0rd", "qwerty123", "xx1234xx", "Dr4g0n"] {
+            assert!(contains_word(s), "{}", s);
+        }
+    }
+
+    #[test]
+    fn not_contains() {
+        for s in &["", "Xk9#vQ2!mZ", "Tr0ub4dor&3", "abc"] {
+            assert!(!contains_word(s), "{}", s);
+        }
+    }
+
+    #[test]
+    fn sorted() {
+        let words: Vec<&str> = words().collect();
+        assert!(words.len() > 300);
+        assert!(words.windows(2).all(|x| x[0] < x[1]));
+        assert!(words.iter().all(|x| x.len() >= 4 && *x == x.to_lowercase()));
+    }
+}
@@ -0,0 +1,108 @@
+//! Locale names
+//!
+//! ```text
+//! language[_territory][.codeset][@modifier]
+//! ```
+//!
+//! Locales supported by glibc. The codeset is compared in the normalized form (lowercase,
+//! alphanumeric characters only), same as glibc does, `en_US.utf8` is equal to `en_US.UTF-8`.
+use std::collections::HashMap;
+
+use lazy_static::lazy_static;
+
+const LOCALES: &str = include_str!("locales.txt");
+
+lazy_static! {
+    // Normalized name -> canonical name
+    static ref NORMALIZED_LOCALES: HashMap<String, &'static str> =
+        locales().map(|x| (normalize(x), x)).collect();
+}
+
+/// Normalizes codeset & modifier, language & territory are kept as they are
+fn normalize(name: &str) -> String {
+    let (name, modifier) = match name.find('@') {
+        Some(idx) => (&name[..idx], &name[idx..]),
+        None => (name, ""),
+    };
+
+    match name.find('.') {
+        Some(idx) => {
+            let codeset: String = name[idx + 1..]
+                .chars()
+                .filter(|c| c.is_ascii_alphanumeric())
+                .map(|c| c.to_ascii_lowercase())
+                .collect();
+            format!("{}.{}{}", &name[..idx], codeset, modifier)
+        }
+        None => format!("{}{}", name, modifier),
+    }
+}
+
+/// Returns all locale names in the alphabetical order
+pub fn locales() -> impl Iterator<Item = &'static str> {
+    super::entries(LOCALES)
+}
+
+pub fn is_valid_locale(name: &str) -> bool {
+    NORMALIZED_LOCALES.contains_key(&normalize(name))
+}
+
+/// Returns canonical locale name (`en_US.utf8` -> `en_US.UTF-8`)
+///
+/// Letter case of the language & territory is ignored, so that it can be used for suggestions.
+pub fn canonical_locale(name: &str) -> Option<&'static str> {
+    let normalized = normalize(name);
+
+    NORMALIZED_LOCALES.get(&normalized).copied().or_else(|| {
+        NORMALIZED_LOCALES
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(&normalized))
+            .map(|(_, v)| *v)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid() {
+        for name in &[
+            "C",
+            "POSIX",
+            "C.UTF-8",
+            "C.utf8",
+            "en_US",
+            "en_US.UTF-8",
+            "en_US.utf8",
+            "de_DE@euro",
+            "ca_ES.UTF-8@valencia",
+            "ca_ES.utf8@valencia",
+        ] {
+            assert!(is_valid_locale(name), "{}", name);
+        }
+    }
+
+    #[test]
+    fn invalid() {
+        for name in &[
+            "",
+            "en",
+            "en_us.UTF-8",
+            "en_US.UTF-16",
+            "xx_XX.UTF-8",
+            "en_US@foo",
+            "# glibc",
+        ] {
+            assert!(!is_valid_locale(name), "{}", name);
+        }
+    }
+
+    #[test]
+    fn canonical() {
+        assert_eq!(canonical_locale("en_US.utf8"), Some("en_US.UTF-8"));
+        assert_eq!(canonical_locale("EN_us.UTF-8"), Some("en_US.UTF-8"));
+        assert_eq!(canonical_locale("ca_es.utf8@valencia"), Some("ca_ES.UTF-8@valencia"));
+        assert_eq!(canonical_locale("xx_XX"), None);
+    }
+}
@@ -0,0 +1,29 @@
+//! Embedded tables of allowed values
+//!
+//! Some types (`timezone`, `locale`) are validated against tables bundled with the crate,
+//! so that the validation works offline and doesn't depend on the host system. Tables are
+//! public, so that the UI can offer allowed values (dropdowns, ...). The `dictionary` table
+//! contains words which passwords must not contain (`noDictionaryWords` keyword).
+//!
+//! # Examples
+//!
+//! ```rust
+//! use reconfix::tables::{locale, timezone};
+//!
+//! assert!(timezone::timezones().any(|x| x == "Europe/Prague"));
+//! assert!(timezone::is_valid_timezone("UTC"));
+//!
+//! assert!(locale::is_valid_locale("en_US.utf8"));
+//! assert_eq!(locale::canonical_locale("en_us.utf8"), Some("en_US.UTF-8"));
+//! ```
+pub mod dictionary;
+pub mod locale;
+pub mod timezone;
+
+/// Table lines without comments & empty lines
+fn entries(table: &'static str) -> impl Iterator<Item = &'static str> {
+    table
+        .lines()
+        .map(str::trim)
+        .filter(|x| !x.is_empty() && !x.starts_with('#'))
+}
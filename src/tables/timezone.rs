@@ -0,0 +1,56 @@
+//! IANA time zone names
+//!
+//! Zones & links (`US/Pacific`, `UTC`, ...) from the tz database. Names are case sensitive.
+const TIMEZONES: &str = include_str!("timezones.txt");
+
+/// Returns all time zone names in the alphabetical order
+pub fn timezones() -> impl Iterator<Item = &'static str> {
+    super::entries(TIMEZONES)
+}
+
+pub fn is_valid_timezone(name: &str) -> bool {
+    timezones().any(|x| x == name)
+}
+
+/// Returns time zone name which differs in the letter case only (`europe/prague` -> `Europe/Prague`)
+pub fn suggest_timezone(name: &str) -> Option<&'static str> {
+    timezones().find(|x| x.eq_ignore_ascii_case(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid() {
+        for name in &[
+            "UTC",
+            "Etc/UTC",
+            "Europe/Prague",
+            "America/Argentina/Buenos_Aires",
+            "US/Pacific",
+        ] {
+            assert!(is_valid_timezone(name), "{}", name);
+        }
+    }
+
+    #[test]
+    fn invalid() {
+        for name in &["", "utc", "Europe/prague", "Mars/Olympus_Mons", "Factory", "# IANA"] {
+            assert!(!is_valid_timezone(name), "{}", name);
+        }
+    }
+
+    #[test]
+    fn sorted() {
+        let timezones: Vec<&str> = timezones().collect();
+        assert!(timezones.len() > 400);
+        assert!(timezones.windows(2).all(|x| x[0] < x[1]));
+    }
+
+    #[test]
+    fn suggest() {
+        assert_eq!(suggest_timezone("europe/prague"), Some("Europe/Prague"));
+        assert_eq!(suggest_timezone("Europe/Pargue"), None);
+    }
+}
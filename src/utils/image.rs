@@ -0,0 +1,158 @@
+//! Image format & dimensions detection from the image header
+//!
+//! Only the header is parsed, the image data itself is not decoded.
+use crate::utils::mime;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ImageInfo {
+    /// Format name (`png`, `jpeg`, ...)
+    pub format: &'static str,
+    pub width: u32,
+    pub height: u32,
+}
+
+fn be_u16(content: &[u8], offset: usize) -> Option<u32> {
+    let bytes = content.get(offset..offset + 2)?;
+    Some(u32::from(bytes[0]) << 8 | u32::from(bytes[1]))
+}
+
+fn be_u32(content: &[u8], offset: usize) -> Option<u32> {
+    let bytes = content.get(offset..offset + 4)?;
+    Some(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+fn le_u16(content: &[u8], offset: usize) -> Option<u32> {
+    let bytes = content.get(offset..offset + 2)?;
+    Some(u32::from(bytes[1]) << 8 | u32::from(bytes[0]))
+}
+
+fn le_u24(content: &[u8], offset: usize) -> Option<u32> {
+    let bytes = content.get(offset..offset + 3)?;
+    Some(u32::from(bytes[2]) << 16 | u32::from(bytes[1]) << 8 | u32::from(bytes[0]))
+}
+
+fn le_u32(content: &[u8], offset: usize) -> Option<u32> {
+    let bytes = content.get(offset..offset + 4)?;
+    Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+fn png_dimensions(content: &[u8]) -> Option<(u32, u32)> {
+    // Signature (8), IHDR length (4), IHDR (4), width (4), height (4)
+    if content.get(12..16)? != b"IHDR" {
+        return None;
+    }
+    Some((be_u32(content, 16)?, be_u32(content, 20)?))
+}
+
+fn jpeg_dimensions(content: &[u8]) -> Option<(u32, u32)> {
+    let mut idx = 2;
+
+    loop {
+        if *content.get(idx)? != 0xff {
+            return None;
+        }
+
+        // Skip fill bytes
+        while *content.get(idx + 1)? == 0xff {
+            idx += 1;
+        }
+
+        let marker = *content.get(idx + 1)?;
+        match marker {
+            // Start of frame (except DHT, JPG & DAC): length (2), precision (1), height (2), width (2)
+            0xc0..=0xcf if marker != 0xc4 && marker != 0xc8 && marker != 0xcc => {
+                return Some((be_u16(content, idx + 7)?, be_u16(content, idx + 5)?));
+            }
+            // Markers without length
+            0x01 | 0xd0..=0xd8 => idx += 2,
+            _ => idx += 2 + be_u16(content, idx + 2)? as usize,
+        };
+    }
+}
+
+fn gif_dimensions(content: &[u8]) -> Option<(u32, u32)> {
+    Some((le_u16(content, 6)?, le_u16(content, 8)?))
+}
+
+fn bmp_dimensions(content: &[u8]) -> Option<(u32, u32)> {
+    // OS/2 BITMAPCOREHEADER has 16 bit dimensions, others 32 bit signed ones
+    if le_u32(content, 14)? == 12 {
+        return Some((le_u16(content, 18)?, le_u16(content, 20)?));
+    }
+
+    let width = le_u32(content, 18)? as i32;
+    let height = le_u32(content, 22)? as i32;
+    Some((width.unsigned_abs(), height.unsigned_abs()))
+}
+
+fn webp_dimensions(content: &[u8]) -> Option<(u32, u32)> {
+    match content.get(12..16)? {
+        b"VP8 " => Some((le_u16(content, 26)? & 0x3fff, le_u16(content, 28)? & 0x3fff)),
+        b"VP8L" => {
+            let bits = le_u32(content, 21)?;
+            Some(((bits & 0x3fff) + 1, ((bits >> 14) & 0x3fff) + 1))
+        }
+        b"VP8X" => Some((le_u24(content, 24)? + 1, le_u24(content, 27)? + 1)),
+        _ => None,
+    }
+}
+
+/// Returns image format & dimensions, `None` if it's not a supported image or the header is invalid
+pub(crate) fn image_info(content: &[u8]) -> Option<ImageInfo> {
+    let (format, (width, height)) = match mime::sniff(content)? {
+        "image/png" => ("png", png_dimensions(content)?),
+        "image/jpeg" => ("jpeg", jpeg_dimensions(content)?),
+        "image/gif" => ("gif", gif_dimensions(content)?),
+        "image/bmp" => ("bmp", bmp_dimensions(content)?),
+        "image/webp" => ("webp", webp_dimensions(content)?),
+        _ => return None,
+    };
+
+    Some(ImageInfo { format, width, height })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(format: &'static str, width: u32, height: u32) -> Option<ImageInfo> {
+        Some(ImageInfo { format, width, height })
+    }
+
+    #[test]
+    fn png() {
+        let content = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR\0\0\x02\x80\0\0\x01\xe0\x08\x06\0\0\0";
+        assert_eq!(image_info(content), info("png", 640, 480));
+    }
+
+    #[test]
+    fn jpeg() {
+        let content = b"\xff\xd8\xff\xe0\0\x04JF\xff\xff\xc2\0\x11\x08\x01\xe0\x02\x80\x03";
+        assert_eq!(image_info(content), info("jpeg", 640, 480));
+    }
+
+    #[test]
+    fn gif() {
+        assert_eq!(image_info(b"GIF89a\x80\x02\xe0\x01\0\0\0"), info("gif", 640, 480));
+    }
+
+    #[test]
+    fn bmp() {
+        let mut content = b"BM\0\0\0\0\0\0\0\0\0\0\0\0\x28\0\0\0\x80\x02\0\0".to_vec();
+        // Top-down bitmap has a negative height
+        content.extend_from_slice(&(-480i32).to_le_bytes());
+        assert_eq!(image_info(&content), info("bmp", 640, 480));
+    }
+
+    #[test]
+    fn webp() {
+        let content = b"RIFF\0\0\0\0WEBPVP8X\x0a\0\0\0\0\0\0\0\x7f\x02\0\xdf\x01\0";
+        assert_eq!(image_info(content), info("webp", 640, 480));
+    }
+
+    #[test]
+    fn truncated_header() {
+        assert_eq!(image_info(b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR\0\0"), None);
+        assert_eq!(image_info(b"\xff\xd8\xff\xe0\0\x10JFIF"), None);
+    }
+}
@@ -0,0 +1,134 @@
+//! Media type detection from file names & content (magic bytes)
+//!
+//! Covers only formats with a reliable signature. Media types which are not listed
+//! here (`text/plain`, `application/octet-stream`, ...) can't be detected.
+
+struct Signature {
+    media_type: &'static str,
+    extensions: &'static [&'static str],
+    /// All (offset, bytes) pairs must match
+    magic: &'static [(usize, &'static [u8])],
+}
+
+const SIGNATURES: &[Signature] = &[
+    Signature {
+        media_type: "image/png",
+        extensions: &["png"],
+        magic: &[(0, b"\x89PNG\r\n\x1a\n")],
+    },
+    Signature {
+        media_type: "image/jpeg",
+        extensions: &["jpg", "jpeg"],
+        magic: &[(0, b"\xff\xd8\xff")],
+    },
+    Signature {
+        media_type: "image/gif",
+        extensions: &["gif"],
+        magic: &[(0, b"GIF87a")],
+    },
+    Signature {
+        media_type: "image/gif",
+        extensions: &["gif"],
+        magic: &[(0, b"GIF89a")],
+    },
+    Signature {
+        media_type: "image/bmp",
+        extensions: &["bmp"],
+        magic: &[(0, b"BM")],
+    },
+    Signature {
+        media_type: "image/webp",
+        extensions: &["webp"],
+        magic: &[(0, b"RIFF"), (8, b"WEBP")],
+    },
+    Signature {
+        media_type: "application/pdf",
+        extensions: &["pdf"],
+        magic: &[(0, b"%PDF-")],
+    },
+    Signature {
+        media_type: "application/zip",
+        extensions: &["zip"],
+        magic: &[(0, b"PK\x03\x04")],
+    },
+    Signature {
+        media_type: "application/gzip",
+        extensions: &["gz", "tgz"],
+        magic: &[(0, b"\x1f\x8b")],
+    },
+    Signature {
+        media_type: "application/x-xz",
+        extensions: &["xz"],
+        magic: &[(0, b"\xfd7zXZ\x00")],
+    },
+];
+
+/// Returns media type detected from the content
+pub(crate) fn sniff(content: &[u8]) -> Option<&'static str> {
+    SIGNATURES
+        .iter()
+        .find(|s| {
+            s.magic
+                .iter()
+                .all(|(offset, bytes)| content.get(*offset..offset + bytes.len()) == Some(*bytes))
+        })
+        .map(|s| s.media_type)
+}
+
+/// Returns media type for the file name extension
+pub(crate) fn media_type_for_name(name: &str) -> Option<&'static str> {
+    let extension = name.rsplit_once('.')?.1.to_lowercase();
+
+    SIGNATURES
+        .iter()
+        .find(|s| s.extensions.contains(&extension.as_str()))
+        .map(|s| s.media_type)
+}
+
+/// Returns `true` if the media type can be detected from the content
+pub(crate) fn is_detectable(media_type: &str) -> bool {
+    SIGNATURES.iter().any(|s| s.media_type.eq_ignore_ascii_case(media_type))
+}
+
+/// Returns `true` if the media type matches the pattern (`image/png`, `image/*`, `*/*`)
+pub(crate) fn matches(media_type: &str, pattern: &str) -> bool {
+    match pattern.strip_suffix("/*") {
+        Some("*") => true,
+        Some(prefix) => media_type
+            .split_once('/')
+            .map(|(x, _)| x.eq_ignore_ascii_case(prefix))
+            .unwrap_or(false),
+        None => media_type.eq_ignore_ascii_case(pattern),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sniff_content() {
+        assert_eq!(sniff(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR"), Some("image/png"));
+        assert_eq!(sniff(b"\xff\xd8\xff\xe0\0\x10JFIF"), Some("image/jpeg"));
+        assert_eq!(sniff(b"RIFF\0\0\0\0WEBPVP8 "), Some("image/webp"));
+        assert_eq!(sniff(b"RIFF\0\0\0\0WAVEfmt "), None);
+        assert_eq!(sniff(b"hello world"), None);
+    }
+
+    #[test]
+    fn name_extension() {
+        assert_eq!(media_type_for_name("logo.PNG"), Some("image/png"));
+        assert_eq!(media_type_for_name("photo.jpeg"), Some("image/jpeg"));
+        assert_eq!(media_type_for_name("notes.txt"), None);
+        assert_eq!(media_type_for_name("png"), None);
+    }
+
+    #[test]
+    fn media_type_pattern() {
+        assert!(matches("image/png", "image/png"));
+        assert!(matches("image/png", "IMAGE/*"));
+        assert!(matches("text/plain", "*/*"));
+        assert!(!matches("text/plain", "image/*"));
+        assert!(!matches("image/png", "image/jpeg"));
+    }
+}
@@ -1,2 +1,6 @@
 pub(crate) mod deref;
+pub(crate) mod image;
+pub(crate) mod mime;
+pub(crate) mod net;
+pub(crate) mod ssh;
 pub(crate) mod value;
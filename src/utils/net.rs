@@ -0,0 +1,60 @@
+//! Network related helpers
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    // ajv v6.7.0 compatible
+    // https://github.com/epoberezkin/ajv/blob/v6.7.0/lib/compile/formats.js
+    pub(crate) static ref HOSTNAME_REGEX: Regex =
+        Regex::new(r"^(?i)[a-z0-9](?:[a-z0-9-]{0,61}[a-z0-9])?(?:\.[a-z0-9](?:[-0-9a-z]{0,61}[0-9a-z])?)*$").unwrap();
+}
+
+const MAX_HOSTNAME_LEN: usize = 255;
+
+// Linux IFNAMSIZ - 1
+const MAX_INTERFACE_NAME_LEN: usize = 15;
+
+/// Checks Linux network interface name
+///
+/// Same rules as the kernel `dev_valid_name`, names can't be longer than 15 bytes, can't
+/// be `.` or `..` and can't contain `/`, `:` or whitespace.
+pub(crate) fn is_valid_interface_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= MAX_INTERFACE_NAME_LEN
+        && name != "."
+        && name != ".."
+        && !name.contains(|c: char| c.is_whitespace() || c == '/' || c == ':')
+}
+
+/// Checks hostname, same rules as the `hostname` type
+pub(crate) fn is_valid_hostname(hostname: &str) -> bool {
+    hostname.chars().count() <= MAX_HOSTNAME_LEN && HOSTNAME_REGEX.is_match(hostname)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hostname() {
+        for hostname in &["localhost", "foo.local", "a-b.example.com", "0.pool.ntp.org"] {
+            assert!(is_valid_hostname(hostname), "{}", hostname);
+        }
+
+        for hostname in &["", "-foo", "foo-", "foo..bar", "foo_bar"] {
+            assert!(!is_valid_hostname(hostname), "{}", hostname);
+        }
+        assert!(!is_valid_hostname(&format!("{}a", "a.".repeat(128))));
+    }
+
+    #[test]
+    fn interface_name() {
+        for name in &["eth0", "wlan0", "enp0s31f6", "br-lan", "wg0.100", "a23456789012345"] {
+            assert!(is_valid_interface_name(name), "{}", name);
+        }
+
+        for name in &["", ".", "..", "a234567890123456", "eth0:1", "eth 0", "eth/0", "eth0\n"] {
+            assert!(!is_valid_interface_name(name), "{}", name);
+        }
+    }
+}
@@ -0,0 +1,380 @@
+//! SSH public keys & `authorized_keys` lines parsing
+//!
+//! https://man.openbsd.org/sshd.8#AUTHORIZED_KEYS_FILE_FORMAT
+use std::fmt;
+
+use base64::Engine;
+
+/// Supported key types (algorithms)
+pub(crate) const KEY_TYPES: &[&str] = &[
+    "ssh-ed25519",
+    "ssh-rsa",
+    "ecdsa-sha2-nistp256",
+    "ecdsa-sha2-nistp384",
+    "ecdsa-sha2-nistp521",
+    "sk-ssh-ed25519@openssh.com",
+    "sk-ecdsa-sha2-nistp256@openssh.com",
+];
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum KeyError {
+    /// Key type or data is missing
+    Incomplete,
+    /// Well formed key of a type which is not supported (`ssh-dss`, certificates, ...)
+    UnsupportedType(String),
+    /// Key type is not known and the key data doesn't embed it
+    UnknownType(String),
+    /// Key data is not a valid base64
+    InvalidData,
+    /// Key type encoded in data doesn't match the key type
+    TypeMismatch,
+    /// Key data doesn't follow the key type wire format
+    Malformed(&'static str),
+    /// Options contain unterminated quoted string
+    UnterminatedQuote,
+}
+
+impl fmt::Display for KeyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            KeyError::Incomplete => write!(f, "expected '<type> <base64 data> [comment]'"),
+            KeyError::UnsupportedType(t) => write!(f, "unsupported key type '{}'", t),
+            KeyError::UnknownType(t) => write!(f, "unknown key type '{}'", t),
+            KeyError::InvalidData => write!(f, "unable to decode key data"),
+            KeyError::TypeMismatch => write!(f, "key data doesn't match key type"),
+            KeyError::Malformed(reason) => write!(f, "malformed key data: {}", reason),
+            KeyError::UnterminatedQuote => write!(f, "unterminated quoted string in options"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct PublicKey<'a> {
+    pub key_type: &'a str,
+    pub data: &'a str,
+    pub comment: Option<&'a str>,
+}
+
+// OpenSSH SSH_RSA_MINIMUM_MODULUS_SIZE
+const RSA_MIN_MODULUS_BITS: usize = 1024;
+const ED25519_KEY_LEN: usize = 32;
+
+/// SSH wire format reader (RFC 4251, section 5)
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn string(&mut self) -> Result<&'a [u8], KeyError> {
+        if self.0.len() < 4 {
+            return Err(KeyError::Malformed("truncated data"));
+        }
+        let len = u32::from_be_bytes([self.0[0], self.0[1], self.0[2], self.0[3]]) as usize;
+
+        if self.0.len() - 4 < len {
+            return Err(KeyError::Malformed("truncated data"));
+        }
+
+        let (value, rest) = self.0[4..].split_at(len);
+        self.0 = rest;
+        Ok(value)
+    }
+
+    /// Positive multiple precision integer, returns number of bits
+    fn positive_mpint(&mut self) -> Result<usize, KeyError> {
+        let value = self.string()?;
+
+        match value {
+            [] => Err(KeyError::Malformed("zero integer")),
+            [x, ..] if x & 0x80 != 0 => Err(KeyError::Malformed("negative integer")),
+            // Leading zero is allowed only if the next byte has the highest bit set
+            [0, x, ..] if x & 0x80 != 0 => Ok((value.len() - 1) * 8),
+            [0, ..] => Err(KeyError::Malformed("integer is not minimally encoded")),
+            [x, ..] => Ok(value.len() * 8 - x.leading_zeros() as usize),
+        }
+    }
+
+    fn finish(&self) -> Result<(), KeyError> {
+        if self.0.is_empty() {
+            Ok(())
+        } else {
+            Err(KeyError::Malformed("trailing data"))
+        }
+    }
+}
+
+/// Validates uncompressed elliptic curve point of the `nistp*` curve
+fn check_ecdsa_point(reader: &mut Reader, curve: &str) -> Result<(), KeyError> {
+    let coordinate_len = match curve {
+        "nistp256" => 32,
+        "nistp384" => 48,
+        "nistp521" => 66,
+        _ => return Err(KeyError::Malformed("unknown curve")),
+    };
+
+    if reader.string()? != curve.as_bytes() {
+        return Err(KeyError::Malformed("curve doesn't match key type"));
+    }
+
+    let point = reader.string()?;
+    if point.len() != 1 + 2 * coordinate_len || point[0] != 0x04 {
+        return Err(KeyError::Malformed("invalid curve point"));
+    }
+
+    Ok(())
+}
+
+/// Validates the rest of the key blob (after the key type)
+fn check_key_blob(key_type: &str, reader: &mut Reader) -> Result<(), KeyError> {
+    match key_type {
+        "ssh-rsa" => {
+            reader.positive_mpint()?;
+            if reader.positive_mpint()? < RSA_MIN_MODULUS_BITS {
+                return Err(KeyError::Malformed("modulus is too small"));
+            }
+        }
+        "ssh-ed25519" | "sk-ssh-ed25519@openssh.com" => {
+            if reader.string()?.len() != ED25519_KEY_LEN {
+                return Err(KeyError::Malformed("invalid ed25519 key length"));
+            }
+        }
+        "sk-ecdsa-sha2-nistp256@openssh.com" => check_ecdsa_point(reader, "nistp256")?,
+        _ => check_ecdsa_point(reader, key_type.trim_start_matches("ecdsa-sha2-"))?,
+    };
+
+    // Security keys have the application (`ssh:`) appended
+    if key_type.starts_with("sk-") {
+        reader.string()?;
+    }
+
+    reader.finish()
+}
+
+/// Returns key type embedded in the base64 encoded key data
+fn embedded_key_type(data: &str) -> Option<String> {
+    let blob = base64::engine::general_purpose::STANDARD.decode(data).ok()?;
+    let key_type = Reader(&blob).string().ok()?;
+    String::from_utf8(key_type.to_vec()).ok()
+}
+
+/// Parses `<type> <base64 data> [comment]`
+pub(crate) fn parse_public_key(s: &str) -> Result<PublicKey<'_>, KeyError> {
+    let s = s.trim();
+
+    let (key_type, rest) = split_whitespace_once(s);
+    let (data, comment) = split_whitespace_once(rest);
+
+    if key_type.is_empty() || data.is_empty() {
+        return Err(KeyError::Incomplete);
+    }
+
+    if !KEY_TYPES.contains(&key_type) {
+        return match embedded_key_type(data) {
+            Some(ref embedded) if embedded == key_type => Err(KeyError::UnsupportedType(key_type.to_string())),
+            _ => Err(KeyError::UnknownType(key_type.to_string())),
+        };
+    }
+
+    let blob = base64::engine::general_purpose::STANDARD
+        .decode(data)
+        .map_err(|_| KeyError::InvalidData)?;
+
+    let mut reader = Reader(&blob);
+    if reader.string().map_err(|_| KeyError::InvalidData)? != key_type.as_bytes() {
+        return Err(KeyError::TypeMismatch);
+    }
+    check_key_blob(key_type, &mut reader)?;
+
+    Ok(PublicKey {
+        key_type,
+        data,
+        comment: if comment.is_empty() { None } else { Some(comment) },
+    })
+}
+
+/// Parses `[options] <type> <base64 data> [comment]` line
+pub(crate) fn parse_authorized_key(s: &str) -> Result<(Vec<&str>, PublicKey<'_>), KeyError> {
+    let s = s.trim();
+
+    // Line starts with the key type if it's a known one or if the key data embeds it
+    let (first, rest) = split_whitespace_once(s);
+    let (data, _) = split_whitespace_once(rest);
+    if KEY_TYPES.contains(&first) || embedded_key_type(data).as_deref() == Some(first) {
+        return Ok((vec![], parse_public_key(s)?));
+    }
+
+    // Options end with the first whitespace outside of quotes
+    let mut options = vec![];
+    let mut start = 0;
+    let mut quoted = false;
+    let mut end = s.len();
+
+    for (idx, c) in s.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            ',' if !quoted => {
+                options.push(&s[start..idx]);
+                start = idx + 1;
+            }
+            c if c.is_whitespace() && !quoted => {
+                end = idx;
+                break;
+            }
+            _ => {}
+        };
+    }
+
+    if quoted {
+        return Err(KeyError::UnterminatedQuote);
+    }
+    options.push(&s[start..end]);
+
+    Ok((options, parse_public_key(&s[end..])?))
+}
+
+fn split_whitespace_once(s: &str) -> (&str, &str) {
+    match s.find(char::is_whitespace) {
+        Some(idx) => (&s[..idx], s[idx..].trim()),
+        None => (s, ""),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ED25519: &str = "AAAAC3NzaC1lZDI1NTE5AAAAIGjhy/7vGVEQWpW8VA6XdeW9pJ9pv6TaX7VyDGx8tHUV";
+    const RSA: &str = "AAAAB3NzaC1yc2EAAAADAQABAAABAQDl534+N798B53VFVkHrsSQ/0yrJvr9SeIl2fcwlyQLdMPjXnu+aZ4BuAnYYWhj7rdlTDgfjXg/ANJG3sM/GzRvUZ1NZEnIo6x2uFX8hfxxEaTwvBY2m0kp2aw+3/c65wA9coa2kBEOVKX7F7iX6HfzrrF0VqCLHZN6Geb66R+hnj3Rv0U9o9fer41DZ6Bp+8V/KXwZ9WRFAZbf9hLj52Wty2D+zzCqov1IVkNajJbrGq7XDGXpdp9vZyURgC3TwKYJQ9jQ4Fko+J4mSLyVWHwOa8WECljVnZ3XbFuS/XxLy05hZMxpUwqGFGN0HxCZi5OBKZ/28igy/fTmGfEiiQxb";
+    const ECDSA_P256: &str = "AAAAE2VjZHNhLXNoYTItbmlzdHAyNTYAAAAIbmlzdHAyNTYAAABBBIrWU2QX9dw/WiWYsm5JgBIN0U1X9cMc5Bvmnkw592jlquwK7FNRDCuu+5T9V7sTruh8cSc8RSM21lMMW1rnRck=";
+    const ECDSA_P521: &str = "AAAAE2VjZHNhLXNoYTItbmlzdHA1MjEAAAAIbmlzdHA1MjEAAACFBAA42MFQXWi3u8vtnQntmctk7aWe4EIHQxuRk53+Cq+YRSs2EiqMlRX00WbgaaFf3quwH4xBk6nUIVknCRpv4jDszAFv7FXx5bh3hYD5IVXnS6wsYaSc6N5O//SJ54c7R2zrFwhqUI6FRuHMS0j9iHJqbJuD9TMMW4hYC/+26YMkIu4Zbg==";
+
+    fn ssh_string(value: &[u8]) -> Vec<u8> {
+        let mut result = (value.len() as u32).to_be_bytes().to_vec();
+        result.extend_from_slice(value);
+        result
+    }
+
+    fn encode(parts: &[&[u8]]) -> String {
+        let blob: Vec<u8> = parts.iter().flat_map(|x| ssh_string(x)).collect();
+        base64::engine::general_purpose::STANDARD.encode(blob)
+    }
+
+    #[test]
+    fn public_key() {
+        let key = format!("ssh-ed25519 {} user@host", ED25519);
+        assert_eq!(
+            parse_public_key(&key),
+            Ok(PublicKey {
+                key_type: "ssh-ed25519",
+                data: ED25519,
+                comment: Some("user@host"),
+            })
+        );
+    }
+
+    #[test]
+    fn public_key_types() {
+        for (key_type, data) in &[
+            ("ssh-rsa", RSA),
+            ("ecdsa-sha2-nistp256", ECDSA_P256),
+            ("ecdsa-sha2-nistp521", ECDSA_P521),
+        ] {
+            assert!(
+                parse_public_key(&format!("{} {}", key_type, data)).is_ok(),
+                "{}",
+                key_type
+            );
+        }
+
+        let sk = encode(&[b"sk-ssh-ed25519@openssh.com", &[1; 32], b"ssh:"]);
+        assert!(parse_public_key(&format!("sk-ssh-ed25519@openssh.com {}", sk)).is_ok());
+    }
+
+    #[test]
+    fn public_key_malformed() {
+        let mut point = vec![0x04];
+        point.extend_from_slice(&[1; 64]);
+
+        for (key_type, data) in &[
+            // Truncated point
+            (
+                "ecdsa-sha2-nistp256",
+                "AAAAE2VjZHNhLXNoYTItbmlzdHAyNTYAAAAIbmlzdHAyNTYAAABBBA==".to_string(),
+            ),
+            (
+                "ecdsa-sha2-nistp256",
+                encode(&[b"ecdsa-sha2-nistp256", b"nistp384", &point]),
+            ),
+            (
+                "ecdsa-sha2-nistp256",
+                encode(&[b"ecdsa-sha2-nistp256", b"nistp256", &point[1..]]),
+            ),
+            ("ssh-ed25519", encode(&[b"ssh-ed25519", &[1; 31]])),
+            // Trailing data
+            ("ssh-ed25519", encode(&[b"ssh-ed25519", &[1; 32], b"x"])),
+            // Missing application
+            (
+                "sk-ssh-ed25519@openssh.com",
+                encode(&[b"sk-ssh-ed25519@openssh.com", &[1; 32]]),
+            ),
+            // Small modulus, negative & non minimal integers
+            ("ssh-rsa", encode(&[b"ssh-rsa", &[1, 0, 1], &[0x7f; 64]])),
+            ("ssh-rsa", encode(&[b"ssh-rsa", &[0x81], &[0x7f; 256]])),
+            ("ssh-rsa", encode(&[b"ssh-rsa", &[0, 1], &[0x7f; 256]])),
+            ("ssh-rsa", encode(&[b"ssh-rsa", &[1, 0, 1]])),
+        ] {
+            assert!(
+                matches!(
+                    parse_public_key(&format!("{} {}", key_type, data)),
+                    Err(KeyError::Malformed(_))
+                ),
+                "{} {}",
+                key_type,
+                data
+            );
+        }
+    }
+
+    #[test]
+    fn public_key_type_mismatch() {
+        let key = format!("ssh-rsa {}", ED25519);
+        assert_eq!(parse_public_key(&key), Err(KeyError::TypeMismatch));
+    }
+
+    #[test]
+    fn public_key_unsupported_type() {
+        let key = format!("ssh-dss {}", encode(&[b"ssh-dss", b"p", b"q", b"g", b"y"]));
+        assert_eq!(
+            parse_public_key(&key),
+            Err(KeyError::UnsupportedType("ssh-dss".to_string()))
+        );
+
+        let key = format!("ssh-dss {}", ED25519);
+        assert_eq!(
+            parse_public_key(&key),
+            Err(KeyError::UnknownType("ssh-dss".to_string()))
+        );
+    }
+
+    #[test]
+    fn authorized_key_unsupported_type() {
+        let line = format!("ssh-dss {} user@host", encode(&[b"ssh-dss", b"p", b"q", b"g", b"y"]));
+        assert_eq!(
+            parse_authorized_key(&line),
+            Err(KeyError::UnsupportedType("ssh-dss".to_string()))
+        );
+
+        let line = format!("no-pty {}", line);
+        assert_eq!(
+            parse_authorized_key(&line),
+            Err(KeyError::UnsupportedType("ssh-dss".to_string()))
+        );
+    }
+
+    #[test]
+    fn authorized_key_with_options() {
+        let line = format!(r#"no-pty,command="echo a, b" ssh-ed25519 {}"#, ED25519);
+        let (options, key) = parse_authorized_key(&line).unwrap();
+        assert_eq!(options, vec!["no-pty", r#"command="echo a, b""#]);
+        assert_eq!(key.data, ED25519);
+        assert_eq!(key.comment, None);
+    }
+}
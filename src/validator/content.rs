@@ -0,0 +1,109 @@
+//! Encoded content validation (`contentEncoding`, `contentMediaType` & `contentSchema`)
+//!
+//! String values can contain structured documents (base64 encoded JSON, embedded YAML, ...).
+//! The content is decoded, parsed according to the media type and validated against the
+//! nested schema. Data paths of nested errors descend into the decoded content.
+//!
+//! `file` values are decoded from the data URI first and the data URI media type is used
+//! if `contentMediaType` is not provided.
+//!
+//! `password` values must never be part of the messages, nested errors are replaced with
+//! a single `contentSchema` error.
+use base64::Engine;
+use serde_json::Value;
+
+use crate::{
+    codec::{Codec, JsonCodec, TomlCodec, YamlCodec},
+    schema::PrimitiveType,
+    validator::{scope::ScopedSchema, state::ValidationState, types::parse_file, Validator},
+};
+
+const CONTENT_SCHEMA_KEYWORD: &str = "contentSchema";
+const DEFAULT_MEDIA_TYPE: &str = "text/plain";
+
+fn decode(encoding: Option<&str>, content: Vec<u8>) -> Result<Vec<u8>, String> {
+    match encoding {
+        None => Ok(content),
+        Some(encoding) if encoding.eq_ignore_ascii_case("base64") => {
+            let content: Vec<u8> = content.into_iter().filter(|x| !x.is_ascii_whitespace()).collect();
+            base64::engine::general_purpose::STANDARD
+                .decode(content)
+                .map_err(|_| "unable to decode base64 content".to_string())
+        }
+        Some(encoding) => Err(format!("unsupported encoding '{}'", encoding)),
+    }
+}
+
+fn parse(media_type: &str, content: &[u8]) -> Result<Value, String> {
+    // Parameters (`; charset=utf-8`) are ignored
+    let essence = media_type.split(';').next().unwrap_or_default().trim().to_lowercase();
+
+    let result = match essence.as_str() {
+        "application/json" => JsonCodec.decode(content),
+        "application/yaml" | "application/x-yaml" | "text/yaml" => YamlCodec.decode(content),
+        "application/toml" => TomlCodec.decode(content),
+        "text/plain" => {
+            return std::str::from_utf8(content)
+                .map(|x| Value::String(x.to_string()))
+                .map_err(|_| "content is not a valid utf-8".to_string());
+        }
+        _ => return Err(format!("unsupported media type '{}'", media_type)),
+    };
+
+    result.map_err(|_| format!("unable to parse content as '{}'", media_type))
+}
+
+pub(crate) fn validate_content(scope: &ScopedSchema, data: &Value) -> ValidationState {
+    let schema = scope.schema();
+
+    if schema.content_encoding().is_none() && schema.content_media_type().is_none() && schema.content_schema().is_none()
+    {
+        return ValidationState::new();
+    }
+
+    // Non string values are handled by the type validation
+    let s = match data.as_str() {
+        Some(x) => x,
+        None => return ValidationState::new(),
+    };
+
+    let (content, media_type) = if *schema.r#type().primitive_type() == PrimitiveType::File {
+        match parse_file(s) {
+            Ok(file) => (file.content, file.media_type),
+            Err(e) => return scope.error("type", e).into(),
+        }
+    } else {
+        (s.as_bytes().to_vec(), DEFAULT_MEDIA_TYPE)
+    };
+
+    let content = match decode(schema.content_encoding(), content) {
+        Ok(x) => x,
+        Err(e) => return scope.error("contentEncoding", e).into(),
+    };
+
+    let media_type = schema.content_media_type().unwrap_or(media_type);
+
+    let value = match parse(media_type, &content) {
+        Ok(x) => x,
+        Err(e) => return scope.error("contentMediaType", e).into(),
+    };
+
+    let content_schema = match schema.content_schema() {
+        Some(x) => x,
+        None => return ValidationState::new(),
+    };
+
+    let state = scope
+        .scope_with_nested_schema(CONTENT_SCHEMA_KEYWORD, content_schema)
+        .validate(Some(&value));
+
+    // Nested messages & data paths are derived from the decoded password, report the content as a whole
+    if *schema.r#type().primitive_type() == PrimitiveType::Password {
+        if state.is_valid() {
+            return ValidationState::new();
+        }
+        return scope.error(CONTENT_SCHEMA_KEYWORD, "invalid content").into();
+    }
+
+    state
+}
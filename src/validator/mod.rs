@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use serde_json::{self, Value};
 
 use crate::{
@@ -8,6 +9,7 @@ use crate::{
 use scope::ScopedSchema;
 use state::ValidationState;
 
+mod content;
 mod error;
 mod path;
 mod scope;
@@ -83,7 +85,7 @@ impl<'a> Validator for ScopedSchema<'a> {
         bail_if_invalid!(validate_const(self, data));
         bail_if_invalid!(validate_enum(self, data));
 
-        match self.schema().r#type().primitive_type() {
+        let mut state = match self.schema().r#type().primitive_type() {
             PrimitiveType::String => types::validate_as_string(self, data),
             PrimitiveType::Array => types::validate_as_array(self, data),
             PrimitiveType::Boolean => types::validate_as_boolean(self, data),
@@ -97,6 +99,8 @@ impl<'a> Validator for ScopedSchema<'a> {
             PrimitiveType::Date => types::validate_as_date(self, data),
             PrimitiveType::DateTime => types::validate_as_datetime(self, data),
             PrimitiveType::Time => types::validate_as_time(self, data),
+            PrimitiveType::Timezone => types::validate_as_timezone(self, data),
+            PrimitiveType::Locale => types::validate_as_locale(self, data),
             PrimitiveType::Email => types::validate_as_email(self, data),
             PrimitiveType::IPv4 => types::validate_as_ipv4(self, data),
             PrimitiveType::IPv6 => types::validate_as_ipv6(self, data),
@@ -106,16 +110,56 @@ impl<'a> Validator for ScopedSchema<'a> {
             PrimitiveType::DNSMasqAddress => types::validate_as_dnsmasq_address(self, data),
             PrimitiveType::IPTablesAddress => types::validate_as_iptables_address(self, data),
             PrimitiveType::StringList => types::validate_as_stringlist(self, data),
+            PrimitiveType::HostEntry => types::validate_as_host_entry(self, data),
+            PrimitiveType::SshPublicKey => types::validate_as_ssh_public_key(self, data),
+            PrimitiveType::Certificate => types::validate_as_certificate(self, data),
+            PrimitiveType::MacAddress => types::validate_as_mac_address(self, data),
+            PrimitiveType::Cidr => types::validate_as_cidr(self, data),
+            PrimitiveType::InterfaceName => types::validate_as_interface_name(self, data),
+            PrimitiveType::WifiSsid => types::validate_as_wifi_ssid(self, data),
+            PrimitiveType::WifiPsk => types::validate_as_wifi_psk(self, data),
+            PrimitiveType::WifiCountry => types::validate_as_wifi_country(self, data),
+            PrimitiveType::Duration => types::validate_as_duration(self, data),
+            PrimitiveType::ByteSize => types::validate_as_bytesize(self, data),
+            PrimitiveType::Semver => types::validate_as_semver(self, data),
+            PrimitiveType::Uuid => types::validate_as_uuid(self, data),
+            PrimitiveType::Regex => types::validate_as_regex(self, data),
+            PrimitiveType::Hex => types::validate_as_hex(self, data),
+            PrimitiveType::Int8 => types::validate_as_int8(self, data),
+            PrimitiveType::Int16 => types::validate_as_int16(self, data),
+            PrimitiveType::Int32 => types::validate_as_int32(self, data),
+            PrimitiveType::Int64 => types::validate_as_int64(self, data),
+            PrimitiveType::UInt8 => types::validate_as_uint8(self, data),
+            PrimitiveType::UInt16 => types::validate_as_uint16(self, data),
+            PrimitiveType::UInt32 => types::validate_as_uint32(self, data),
+            PrimitiveType::UInt64 => types::validate_as_uint64(self, data),
+        };
+
+        // Not using bail_if_invalid, warnings of the valid state must be kept
+        if state.is_valid() {
+            state.extend(content::validate_content(self, data));
         }
+
+        state
     }
 }
 
 impl Validator for Schema {
     fn validate(&self, data: Option<&Value>) -> ValidationState {
-        ScopedSchema::new(self).validate(data)
+        ScopedSchema::new(self).with_root(data).validate(data)
     }
 }
 
 pub fn validate(schema: &Schema, data: &Value) -> ValidationState {
     schema.validate(Some(data))
 }
+
+/// Validates data against the schema with the injected current date & time
+///
+/// Relative date & time limits (`min: now-30d`, ...) are resolved against `now` instead of
+/// the system clock.
+pub fn validate_at(schema: &Schema, data: &Value, now: DateTime<Utc>) -> ValidationState {
+    ScopedSchema::with_now(schema, now)
+        .with_root(Some(data))
+        .validate(Some(data))
+}
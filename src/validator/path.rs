@@ -1,5 +1,7 @@
 use std::fmt;
 
+use balena_temen::ast::{Identifier, IdentifierValue};
+
 #[derive(Debug, Clone)]
 pub enum Component {
     Property(String),
@@ -26,6 +28,20 @@ impl PathBuf {
     {
         self.components.push(Component::Property(property.into()))
     }
+
+    /// Returns an identifier used as a position for the relative (`super.network`) formulas
+    pub fn to_identifier(&self) -> Identifier {
+        let values = self
+            .components
+            .iter()
+            .map(|x| match x {
+                Component::Property(s) => IdentifierValue::Name(s.clone()),
+                Component::Index(idx) => IdentifierValue::Index(*idx as isize),
+            })
+            .collect();
+
+        Identifier::new(values)
+    }
 }
 
 impl fmt::Display for PathBuf {
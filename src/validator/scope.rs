@@ -1,3 +1,6 @@
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+
 use crate::{
     schema::{Property, Schema},
     validator::{error::ValidationError, path::PathBuf},
@@ -8,17 +11,40 @@ pub struct ScopedSchema<'a> {
     schema: &'a Schema,
     schema_path: PathBuf,
     data_path: PathBuf,
+    now: DateTime<Utc>,
+    root: Option<&'a Value>,
 }
 
 impl<'a> ScopedSchema<'a> {
     pub fn new(schema: &Schema) -> ScopedSchema {
+        ScopedSchema::with_now(schema, Utc::now())
+    }
+
+    /// Creates scoped schema with the injected current date & time
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - An instant used to resolve relative (`now-1d`, ...) limits
+    pub fn with_now(schema: &Schema, now: DateTime<Utc>) -> ScopedSchema<'_> {
         ScopedSchema {
             schema,
             schema_path: PathBuf::new(),
             data_path: PathBuf::new(),
+            now,
+            root: None,
         }
     }
 
+    /// Sets the root data, formulas (`inNetwork`, ...) are evaluated against it
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - Validated data
+    pub fn with_root(mut self, root: Option<&'a Value>) -> ScopedSchema<'a> {
+        self.root = root;
+        self
+    }
+
     pub fn schema(&self) -> &Schema {
         &self.schema
     }
@@ -30,6 +56,14 @@ impl<'a> ScopedSchema<'a> {
     pub fn data_path(&self) -> &PathBuf {
         &self.data_path
     }
+
+    pub fn now(&self) -> DateTime<Utc> {
+        self.now
+    }
+
+    pub fn root(&self) -> Option<&Value> {
+        self.root
+    }
 }
 
 impl<'a> ScopedSchema<'a> {
@@ -41,10 +75,28 @@ impl<'a> ScopedSchema<'a> {
             schema: self.schema,
             schema_path: self.schema_path.clone(),
             data_path,
+            now: self.now,
+            root: self.root,
+        }
+    }
+
+    pub fn scope_with_data_property<S: Into<String>>(&self, property: S) -> ScopedSchema<'_> {
+        let mut data_path = self.data_path.clone();
+        data_path.push_property(property);
+
+        ScopedSchema {
+            schema: self.schema,
+            schema_path: self.schema_path.clone(),
+            data_path,
+            now: self.now,
+            root: self.root,
         }
     }
 
-    pub fn scope_with_property<'b>(&self, index: usize, property: &'b Property) -> ScopedSchema<'b> {
+    pub fn scope_with_property<'b>(&self, index: usize, property: &'b Property) -> ScopedSchema<'b>
+    where
+        'a: 'b,
+    {
         let mut data_path = self.data_path.clone();
         data_path.push_property(property.name());
 
@@ -57,10 +109,15 @@ impl<'a> ScopedSchema<'a> {
             schema: property.schema(),
             schema_path,
             data_path,
+            now: self.now,
+            root: self.root,
         }
     }
 
-    pub fn scope_with_schema_index<'b>(&self, index: usize, schema: &'b Schema) -> ScopedSchema<'b> {
+    pub fn scope_with_schema_index<'b>(&self, index: usize, schema: &'b Schema) -> ScopedSchema<'b>
+    where
+        'a: 'b,
+    {
         let mut schema_path = self.schema_path.clone();
         schema_path.push_index(index);
 
@@ -68,6 +125,24 @@ impl<'a> ScopedSchema<'a> {
             schema,
             schema_path,
             data_path: self.data_path.clone(),
+            now: self.now,
+            root: self.root,
+        }
+    }
+
+    pub fn scope_with_nested_schema<'b, S: Into<String>>(&self, keyword: S, schema: &'b Schema) -> ScopedSchema<'b>
+    where
+        'a: 'b,
+    {
+        let mut schema_path = self.schema_path.clone();
+        schema_path.push_property(keyword);
+
+        ScopedSchema {
+            schema,
+            schema_path,
+            data_path: self.data_path.clone(),
+            now: self.now,
+            root: self.root,
         }
     }
 
@@ -79,6 +154,8 @@ impl<'a> ScopedSchema<'a> {
             schema: self.schema,
             schema_path,
             data_path: self.data_path.clone(),
+            now: self.now,
+            root: self.root,
         }
     }
 }
@@ -3,15 +3,17 @@ use crate::validator::error::ValidationError;
 #[derive(Debug, Default)]
 pub struct ValidationState {
     errors: Vec<ValidationError>,
+    // Suspicious, but valid, values
+    warnings: Vec<ValidationError>,
 }
 
 impl ValidationState {
     pub fn new() -> ValidationState {
-        ValidationState { errors: vec![] }
+        ValidationState::default()
     }
 
     pub fn new_with_error(error: ValidationError) -> ValidationState {
-        ValidationState { errors: vec![error] }
+        ValidationState::new_with_errors(vec![error])
     }
 
     pub fn new_with_errors<I>(errors: I) -> ValidationState
@@ -20,6 +22,7 @@ impl ValidationState {
     {
         ValidationState {
             errors: errors.into_iter().collect(),
+            warnings: vec![],
         }
     }
 
@@ -29,6 +32,12 @@ impl ValidationState {
 
     pub fn extend(&mut self, other: ValidationState) {
         self.errors.extend(other.errors);
+        self.warnings.extend(other.warnings);
+    }
+
+    /// Warnings do not affect validity
+    pub fn push_warning(&mut self, warning: ValidationError) {
+        self.warnings.push(warning)
     }
 
     pub fn is_valid(&self) -> bool {
@@ -38,11 +47,15 @@ impl ValidationState {
     pub fn errors(&self) -> &Vec<ValidationError> {
         &self.errors
     }
+
+    pub fn warnings(&self) -> &Vec<ValidationError> {
+        &self.warnings
+    }
 }
 
 impl From<ValidationError> for ValidationState {
     fn from(error: ValidationError) -> ValidationState {
-        ValidationState { errors: vec![error] }
+        ValidationState::new_with_error(error)
     }
 }
 
@@ -51,8 +64,6 @@ where
     T: IntoIterator<Item = ValidationError>,
 {
     fn from(iter: T) -> Self {
-        ValidationState {
-            errors: iter.into_iter().collect(),
-        }
+        ValidationState::new_with_errors(iter)
     }
 }
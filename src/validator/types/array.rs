@@ -132,6 +132,8 @@ pub fn validate_as_array(scope: &ScopedSchema, data: &Value) -> ValidationState
         let data_scope = scope.scope_with_data_index(idx);
 
         let mut data_item_state = ValidationState::new();
+        // Keeps warnings of the matching schema
+        let mut valid_item_state = ValidationState::new();
 
         for (idx, array_schema) in scope.schema().items().iter().enumerate() {
             let nested_scope = data_scope.scope_with_schema_index(idx, array_schema);
@@ -140,6 +142,7 @@ pub fn validate_as_array(scope: &ScopedSchema, data: &Value) -> ValidationState
 
             if nested_state.is_valid() {
                 valid_count += 1;
+                valid_item_state.extend(nested_state);
             } else {
                 data_item_state.extend(nested_state);
             }
@@ -147,7 +150,7 @@ pub fn validate_as_array(scope: &ScopedSchema, data: &Value) -> ValidationState
 
         match valid_count {
             0 => state.extend(data_item_state),
-            1 => {}
+            1 => state.extend(valid_item_state),
             _ => state.push_error(data_scope.error("items", "valid against multiple schemas")),
         };
     }
@@ -0,0 +1,37 @@
+use serde_json::Value;
+
+use crate::{
+    parser::bytesize::ByteSize,
+    schema::Limit,
+    validator::{
+        scope::ScopedSchema,
+        state::ValidationState,
+        types::{validate_as_string, validate_limits},
+    },
+};
+
+// `min` & `max` are sizes (`512MiB`) or numbers of bytes
+
+fn parse_limit(limit: &Limit) -> Result<ByteSize, String> {
+    match limit {
+        Limit::String(s) => s.parse().map_err(|e: crate::error::Error| e.message().to_string()),
+        Limit::Number(n) => n
+            .as_u64()
+            .map(ByteSize::from)
+            .ok_or_else(|| "expected non negative number of bytes".to_string()),
+    }
+}
+
+pub fn validate_as_bytesize(scope: &ScopedSchema, data: &Value) -> ValidationState {
+    let mut state = validate_as_string(scope, data);
+    if !state.is_valid() {
+        return state;
+    }
+
+    match data.as_str().expect("invalid validate_as_string").parse::<ByteSize>() {
+        Ok(size) => state.extend(validate_limits(scope, &size, parse_limit)),
+        Err(e) => state.push_error(scope.error("type", format!("expected 'bytesize': {}", e.message()))),
+    };
+
+    state
+}
@@ -0,0 +1,176 @@
+use std::borrow::Cow;
+
+use base64::Engine;
+use chrono::{TimeZone, Utc};
+use serde_json::Value;
+use x509_parser::{certificate::X509Certificate, pem::Pem};
+
+use crate::validator::{
+    scope::ScopedSchema,
+    state::ValidationState,
+    types::{datetime::parse_datetime_limit, validate_as_string},
+};
+
+// PEM encoded X.509 certificate(s)
+//
+//   -----BEGIN CERTIFICATE-----
+//   MIIBezCCASGgAwIBAgIUQG6i...
+//   -----END CERTIFICATE-----
+//
+// The whole PEM can be base64 encoded as well (balenaRootCA). Multiple certificates
+// (bundle) are allowed and keywords are applied to every certificate. Every certificate
+// in the bundle must be self-signed, signed by the next one or by a CA in the bundle.
+
+const PEM_PREFIX: &str = "-----BEGIN ";
+const CERTIFICATE_LABEL: &str = "CERTIFICATE";
+
+/// Returns PEM content, decodes it if it's base64 wrapped
+fn pem_content(s: &str) -> Option<Cow<'_, [u8]>> {
+    let s = s.trim();
+
+    if s.starts_with(PEM_PREFIX) {
+        return Some(Cow::Borrowed(s.as_bytes()));
+    }
+
+    let encoded: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+    let decoded = base64::engine::general_purpose::STANDARD.decode(encoded).ok()?;
+
+    if String::from_utf8_lossy(&decoded).trim_start().starts_with(PEM_PREFIX) {
+        Some(Cow::Owned(decoded))
+    } else {
+        None
+    }
+}
+
+fn format_timestamp(timestamp: i64) -> String {
+    Utc.timestamp_opt(timestamp, 0)
+        .single()
+        .map(|x| x.to_rfc3339())
+        .unwrap_or_else(|| timestamp.to_string())
+}
+
+fn validate_certificate(scope: &ScopedSchema, name: &str, certificate: &X509Certificate) -> ValidationState {
+    let schema = scope.schema();
+    let mut state = ValidationState::new();
+
+    if let Some(limit) = schema.not_expired_at() {
+        let now = scope.now();
+
+        match parse_datetime_limit(limit, now) {
+            Ok(at) => {
+                let validity = certificate.validity();
+                let not_before = validity.not_before.timestamp();
+                let not_after = validity.not_after.timestamp();
+
+                if not_before > now.timestamp() {
+                    state.push_error(scope.error(
+                        "notExpiredAt",
+                        format!("{} is not valid before '{}'", name, format_timestamp(not_before)),
+                    ));
+                }
+
+                if not_after < at.timestamp() {
+                    state.push_error(scope.error(
+                        "notExpiredAt",
+                        format!("{} expires at '{}'", name, format_timestamp(not_after)),
+                    ));
+                }
+            }
+            Err(e) => state.push_error(scope.error("notExpiredAt", format!("invalid 'notExpiredAt' value: {}", e))),
+        };
+    }
+
+    if let Some(is_ca) = schema.is_ca() {
+        if certificate.is_ca() != is_ca {
+            let expected = if is_ca { "a CA" } else { "not a CA" };
+            state.push_error(scope.error("isCA", format!("{} is expected to be {}", name, expected)));
+        }
+    }
+
+    state
+}
+
+/// Checks that the certificate is self-signed, signed by the next one or by any CA in the bundle
+///
+/// The last certificate can be issued by a CA outside of the bundle (full chain without
+/// the root CA), unless the issuer is in the bundle.
+fn is_signed_in_bundle(idx: usize, certificates: &[X509Certificate]) -> bool {
+    let certificate = &certificates[idx];
+    let is_signed_by = |issuer: &X509Certificate| certificate.verify_signature(Some(issuer.public_key())).is_ok();
+
+    // Self-signed certificates don't have to be CAs (v1 roots, device certificates, ...)
+    if is_signed_by(certificate) || certificates.get(idx + 1).map(is_signed_by).unwrap_or(false) {
+        return true;
+    }
+
+    if certificates.iter().filter(|x| x.is_ca()).any(is_signed_by) {
+        return true;
+    }
+
+    idx + 1 == certificates.len() && certificates.iter().all(|x| x.subject() != certificate.issuer())
+}
+
+pub fn validate_as_certificate(scope: &ScopedSchema, data: &Value) -> ValidationState {
+    let mut state = validate_as_string(scope, data);
+
+    if !state.is_valid() {
+        return state;
+    }
+
+    let content = match pem_content(data.as_str().expect("invalid validate_as_string")) {
+        Some(x) => x,
+        None => return scope.error("type", "expected PEM encoded 'certificate'").into(),
+    };
+
+    let mut pems = vec![];
+
+    for (idx, pem) in Pem::iter_from_buffer(&content).enumerate() {
+        let name = format!("certificate #{}", idx + 1);
+
+        match pem {
+            Ok(x) if x.label == CERTIFICATE_LABEL => pems.push((name, x)),
+            Ok(x) => state.push_error(scope.error("type", format!("{}: unexpected '{}' PEM block", name, x.label))),
+            Err(_) => {
+                state.push_error(scope.error("type", format!("{}: unable to decode PEM", name)));
+                break;
+            }
+        };
+    }
+
+    if pems.is_empty() && state.is_valid() {
+        state.push_error(scope.error("type", "expected PEM encoded 'certificate'"));
+    }
+
+    let mut certificates = vec![];
+
+    for (name, pem) in &pems {
+        match pem.parse_x509() {
+            Ok(certificate) => {
+                let name = format!("{} ({})", name, certificate.subject());
+                state.extend(validate_certificate(scope, &name, &certificate));
+                certificates.push((name, certificate));
+            }
+            Err(_) => state.push_error(scope.error("type", format!("{}: unable to parse X.509", name))),
+        };
+    }
+
+    if !state.is_valid() {
+        return state;
+    }
+
+    let (names, certificates): (Vec<_>, Vec<_>) = certificates.into_iter().unzip();
+
+    for (idx, name) in names.iter().enumerate() {
+        if !is_signed_in_bundle(idx, &certificates) {
+            state.push_error(scope.error(
+                "type",
+                format!(
+                    "{} is not signed by the next certificate or by a CA in the bundle",
+                    name
+                ),
+            ));
+        }
+    }
+
+    state
+}
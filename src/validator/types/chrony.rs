@@ -1,9 +1,8 @@
 use serde_json::Value;
 
-use crate::validator::{
-    scope::ScopedSchema,
-    state::ValidationState,
-    types::{validate_as_hostname, validate_as_ipv4, validate_as_ipv6},
+use crate::{
+    parser::chrony::Server,
+    validator::{scope::ScopedSchema, state::ValidationState, types::validate_as_string},
 };
 
 // https://github.com/balena-os/meta-balena/blob/v2.29.2/meta-resin-common/recipes-connectivity/resin-ntp-config/resin-ntp-config/resin-ntp-config#L19
@@ -17,18 +16,19 @@ use crate::validator::{
 //     An example of using this command is shown below:
 //     add server foo.example.net minpoll 6 maxpoll 10 key 25
 //
-// Additional options not supported, validate either as ipv4, ipv6 or hostname
+// Address (ipv4, ipv6 or hostname) & all server directive options are validated, see
+// the `parser::chrony` module for details.
 
 pub fn validate_as_chrony_address(scope: &ScopedSchema, data: &Value) -> ValidationState {
-    let state = validate_as_hostname(scope, data);
-    if state.is_valid() {
+    let mut state = validate_as_string(scope, data);
+
+    if !state.is_valid() {
         return state;
     }
 
-    let state = validate_as_ipv4(scope, data);
-    if state.is_valid() {
-        return state;
+    if let Err(e) = data.as_str().expect("invalid validate_as_string").parse::<Server>() {
+        state.push_error(scope.error("type", format!("expected 'chrony-address': {}", e.message())));
     }
 
-    validate_as_ipv6(scope, data)
+    state
 }
@@ -0,0 +1,56 @@
+use serde_json::Value;
+
+use crate::{
+    parser::cidr::Cidr,
+    schema::IpFamily,
+    validator::{
+        scope::ScopedSchema,
+        state::ValidationState,
+        types::{validate_as_string, validate_flag},
+    },
+};
+
+// `private` & `multicast` keywords are satisfied only if the whole network is within the
+// private (multicast) address space.
+
+pub fn validate_as_cidr(scope: &ScopedSchema, data: &Value) -> ValidationState {
+    let mut state = validate_as_string(scope, data);
+    if !state.is_valid() {
+        return state;
+    }
+
+    let cidr = match data.as_str().expect("invalid validate_as_string").parse::<Cidr>() {
+        Ok(x) => x,
+        Err(e) => return scope.error("type", format!("expected 'cidr': {}", e.message())).into(),
+    };
+
+    let schema = scope.schema();
+
+    if let Some(family) = schema.family() {
+        let actual = if cidr.network().is_ipv4() {
+            IpFamily::IPv4
+        } else {
+            IpFamily::IPv6
+        };
+
+        if family != actual {
+            state.push_error(scope.error("family", format!("expected '{}' network, got '{}'", family, actual)));
+        }
+    }
+
+    state.extend(validate_flag(
+        scope,
+        "private",
+        schema.private(),
+        cidr.is_private(),
+        "private network",
+    ));
+    state.extend(validate_flag(
+        scope,
+        "multicast",
+        schema.multicast(),
+        cidr.is_multicast(),
+        "multicast network",
+    ));
+    state
+}
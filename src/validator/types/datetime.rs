@@ -1,9 +1,24 @@
-use chrono;
+use std::fmt;
+
+use chrono::{
+    self,
+    format::{self as chrono_format, Parsed},
+    DateTime, FixedOffset, NaiveDate, NaiveTime, TimeZone, Utc,
+};
 use lazy_static::lazy_static;
 use regex::Regex;
 use serde_json::Value;
 
-use crate::validator::{scope::ScopedSchema, state::ValidationState, types::validate_as_string};
+use crate::{
+    error::Error,
+    parser::duration::Duration,
+    schema::{DateTimeFormat, Limit},
+    validator::{
+        scope::ScopedSchema,
+        state::ValidationState,
+        types::{validate_as_string, validate_limits},
+    },
+};
 
 lazy_static! {
     // ajv v6.7.0 compatible
@@ -17,31 +32,212 @@ lazy_static! {
         Regex::new(r"^(\d\d):(\d\d):(\d\d)(\.\d+)?(z|[+-]\d\d:\d\d)?$").unwrap();
 }
 
-pub fn validate_as_datetime(scope: &ScopedSchema, data: &Value) -> ValidationState {
-    let mut state = validate_as_string(scope, data);
+/// Time of day with an optional offset
+#[derive(Debug, Clone, Copy)]
+struct TimeOfDay {
+    time: NaiveTime,
+    offset: Option<FixedOffset>,
+}
 
-    if state.is_valid()
-        && chrono::DateTime::parse_from_rfc3339(data.as_str().expect("invalid validate_as_string")).is_err()
-    {
-        state.push_error(scope.error("type", "unable to parse as 'datetime'"));
+impl TimeOfDay {
+    /// Time comparable with the `other` one
+    ///
+    /// Times are converted to UTC only if both of them have an offset, otherwise they're
+    /// compared as written.
+    fn comparable_with(&self, other: &TimeOfDay) -> NaiveTime {
+        match (self.offset, other.offset) {
+            (Some(offset), Some(_)) => {
+                let offset = chrono::Duration::seconds(i64::from(offset.local_minus_utc()));
+                self.time.overflowing_sub_signed(offset).0
+            }
+            _ => self.time,
+        }
     }
 
-    state
+    fn is_before(&self, other: &TimeOfDay) -> bool {
+        self.comparable_with(other) < other.comparable_with(self)
+    }
 }
 
-fn is_leap_year(year: usize) -> bool {
-    // https://tools.ietf.org/html/rfc3339#appendix-C
-    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+impl fmt::Display for TimeOfDay {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.offset {
+            Some(offset) => write!(f, "{}{}", self.time, offset),
+            None => write!(f, "{}", self.time),
+        }
+    }
 }
 
-fn days(year: usize, month: usize) -> usize {
-    const DAYS: [usize; 12] = [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+fn parse_with_format(s: &str, format: &DateTimeFormat) -> Result<Parsed, String> {
+    let mut parsed = Parsed::new();
+    chrono_format::parse(&mut parsed, s, format.items()).map_err(|e| e.to_string())?;
+    Ok(parsed)
+}
+
+fn parse_date(s: &str) -> Result<NaiveDate, &'static str> {
+    let captures = DATE_REGEX.captures(s).ok_or("expected `date`")?;
+
+    let year: i32 = captures[1].parse().expect("invalid regex");
+    let month: u32 = captures[2].parse().expect("invalid regex");
+    let day: u32 = captures[3].parse().expect("invalid regex");
 
-    if month == 2 && is_leap_year(year) {
-        29
+    NaiveDate::from_ymd_opt(year, month, day).ok_or("invalid `date` range")
+}
+
+fn parse_time(s: &str) -> Result<TimeOfDay, &'static str> {
+    let captures = TIME_REGEX.captures(s).ok_or("expected `time`")?;
+
+    let hour: u32 = captures[1].parse().expect("invalid regex");
+    let min: u32 = captures[2].parse().expect("invalid regex");
+    let sec: u32 = captures[3].parse().expect("invalid regex");
+
+    if !((hour <= 23 && min <= 59 && sec <= 59) || (hour == 23 && min == 59 && sec == 60)) {
+        return Err("invalid `time` range");
+    }
+
+    // Digits beyond the nanosecond precision are ignored
+    let nanos: u32 = match captures.get(4) {
+        Some(fraction) => {
+            let digits = &fraction.as_str()[1..];
+            format!("{:0<9}", &digits[..digits.len().min(9)])
+                .parse()
+                .expect("invalid regex")
+        }
+        None => 0,
+    };
+
+    // chrono represents the leap second as the second 59 with >= 1s fraction
+    let time = if sec == 60 {
+        NaiveTime::from_hms_nano_opt(hour, min, 59, 1_000_000_000 + nanos)
     } else {
-        DAYS[month - 1]
+        NaiveTime::from_hms_nano_opt(hour, min, sec, nanos)
     }
+    .ok_or("invalid `time` range")?;
+
+    let offset = match captures.get(5).map(|x| x.as_str()) {
+        Some("z") => Some(FixedOffset::east_opt(0).expect("invalid offset")),
+        Some(x) => {
+            let hours: i32 = x[1..3].parse().expect("invalid regex");
+            let minutes: i32 = x[4..6].parse().expect("invalid regex");
+            let seconds = (hours * 60 + minutes) * 60;
+            let offset = if x.starts_with('-') {
+                FixedOffset::west_opt(seconds)
+            } else {
+                FixedOffset::east_opt(seconds)
+            };
+            Some(offset.ok_or("invalid `time` offset")?)
+        }
+        None => None,
+    };
+
+    Ok(TimeOfDay { time, offset })
+}
+
+/// Resolves `now`, `now+<duration>` & `now-<duration>` limits, `None` if the limit is absolute
+fn resolve_relative(s: &str, now: DateTime<Utc>) -> Option<Result<DateTime<Utc>, String>> {
+    let offset = s.strip_prefix("now")?;
+
+    if offset.is_empty() {
+        return Some(Ok(now));
+    }
+
+    let (sign, duration) = match (offset.strip_prefix('+'), offset.strip_prefix('-')) {
+        (Some(duration), _) => (1, duration),
+        (_, Some(duration)) => (-1, duration),
+        _ => return Some(Err("expected 'now', 'now+<duration>' or 'now-<duration>'".to_string())),
+    };
+
+    let result = duration
+        .parse::<Duration>()
+        .map_err(|e: Error| e.message().to_string())
+        .and_then(|x| chrono::Duration::from_std(x.as_std()).map_err(|_| "duration is too long".to_string()))
+        .and_then(|x| {
+            if sign > 0 {
+                now.checked_add_signed(x)
+            } else {
+                now.checked_sub_signed(x)
+            }
+            .ok_or_else(|| "date & time is out of range".to_string())
+        });
+
+    Some(result)
+}
+
+pub(crate) fn parse_datetime_limit(limit: &Limit, now: DateTime<Utc>) -> Result<DateTime<Utc>, String> {
+    let s = limit.as_str().ok_or("expected 'datetime' or 'now[+-<duration>]'")?;
+
+    resolve_relative(s, now).unwrap_or_else(|| {
+        DateTime::parse_from_rfc3339(s)
+            .map(|x| x.with_timezone(&Utc))
+            .map_err(|e| e.to_string())
+    })
+}
+
+fn parse_date_limit(limit: &Limit, now: DateTime<Utc>) -> Result<NaiveDate, String> {
+    let s = limit.as_str().ok_or("expected 'date' or 'now[+-<duration>]'")?;
+
+    match resolve_relative(s, now) {
+        Some(x) => x.map(|x| x.date_naive()),
+        None => parse_date(s).map_err(str::to_string),
+    }
+}
+
+fn parse_time_limit(limit: &Limit) -> Result<TimeOfDay, String> {
+    let s = limit.as_str().ok_or("expected 'time'")?;
+
+    if s.starts_with("now") {
+        return Err("relative limits are not supported by 'time'".to_string());
+    }
+
+    parse_time(s).map_err(str::to_string)
+}
+
+pub fn validate_as_datetime(scope: &ScopedSchema, data: &Value) -> ValidationState {
+    let mut state = validate_as_string(scope, data);
+    if !state.is_valid() {
+        return state;
+    }
+
+    let s = data.as_str().expect("invalid validate_as_string");
+    let schema = scope.schema();
+
+    let datetime = match schema.format() {
+        Some(format) => {
+            let parsed = match parse_with_format(s, format) {
+                Ok(x) => x,
+                Err(e) => {
+                    state.push_error(scope.error("type", format!("unable to parse as 'datetime' ({}): {}", format, e)));
+                    return state;
+                }
+            };
+
+            if parsed.offset().is_none() && schema.timezone_required() {
+                state.push_error(scope.error("timezoneRequired", "expected time zone offset"));
+                return state;
+            }
+
+            // Values without the offset are in UTC
+            match parsed.offset() {
+                Some(_) => parsed.to_datetime().map(|x| x.with_timezone(&Utc)),
+                None => parsed
+                    .to_naive_datetime_with_offset(0)
+                    .map(|x| Utc.from_utc_datetime(&x)),
+            }
+            .map_err(|e| format!("unable to parse as 'datetime' ({}): {}", format, e))
+        }
+        None => DateTime::parse_from_rfc3339(s)
+            .map(|x| x.with_timezone(&Utc))
+            .map_err(|_| "unable to parse as 'datetime'".to_string()),
+    };
+
+    match datetime {
+        Ok(x) => state.extend(validate_limits(scope, &x, |limit| {
+            parse_datetime_limit(limit, scope.now())
+        })),
+        Err(e) => state.push_error(scope.error("type", e)),
+    };
+
+    state
 }
 
 pub fn validate_as_date(scope: &ScopedSchema, data: &Value) -> ValidationState {
@@ -50,40 +246,83 @@ pub fn validate_as_date(scope: &ScopedSchema, data: &Value) -> ValidationState {
         None => return scope.error("type", "expected `date`").into(),
     };
 
-    let captures = match DATE_REGEX.captures(s) {
-        Some(x) => x,
-        _ => return scope.error("type", "expected `date`").into(),
+    let date = match scope.schema().format() {
+        Some(format) => parse_with_format(s, format)
+            .and_then(|x| x.to_naive_date().map_err(|e| e.to_string()))
+            .map_err(|e| format!("unable to parse as 'date' ({}): {}", format, e)),
+        None => parse_date(s).map_err(str::to_string),
     };
 
-    let year: usize = (&captures[1]).parse().expect("invalid regex");
-    let month: usize = (&captures[2]).parse().expect("invalid regex");
-    let day: usize = (&captures[3]).parse().expect("invalid regex");
-
-    if month >= 1 && month <= 12 && day >= 1 && day <= days(year, month) {
-        ValidationState::new()
-    } else {
-        scope.error("type", "invalid `date` range").into()
+    match date {
+        Ok(x) => validate_limits(scope, &x, |limit| parse_date_limit(limit, scope.now())),
+        Err(e) => scope.error("type", e).into(),
     }
 }
 
+fn validate_time_limits(scope: &ScopedSchema, time: &TimeOfDay) -> ValidationState {
+    let schema = scope.schema();
+    let mut state = ValidationState::new();
+
+    let mut limit = |keyword: &str, limit: Option<&Limit>| match limit.map(parse_time_limit) {
+        Some(Ok(x)) => Some(x),
+        Some(Err(e)) => {
+            state.push_error(scope.error(keyword, format!("invalid '{}' value: {}", keyword, e)));
+            None
+        }
+        None => None,
+    };
+
+    let min = limit("min", schema.min_limit());
+    let max = limit("max", schema.max_limit());
+
+    match (min, max) {
+        // Window crosses the midnight (`min: 22:00:00`, `max: 06:00:00`)
+        (Some(min), Some(max)) if max.is_before(&min) => {
+            if time.is_before(&min) && max.is_before(time) {
+                state.push_error(scope.error("min", format!("expected '>= {}' or '<= {}', got '{}'", min, max, time)));
+            }
+        }
+        _ => {
+            if let Some(min) = min.filter(|x| time.is_before(x)) {
+                state.push_error(scope.error("min", format!("expected '>= {}', got '{}'", min, time)));
+            }
+
+            if let Some(max) = max.filter(|x| x.is_before(time)) {
+                state.push_error(scope.error("max", format!("expected '<= {}', got '{}'", max, time)));
+            }
+        }
+    };
+
+    state
+}
+
 pub fn validate_as_time(scope: &ScopedSchema, data: &Value) -> ValidationState {
     let s = match data.as_str() {
         Some(x) => x,
         None => return scope.error("type", "expected `time`").into(),
     };
 
-    let captures = match TIME_REGEX.captures(s) {
-        Some(x) => x,
-        _ => return scope.error("type", "expected `time`").into(),
+    let schema = scope.schema();
+
+    let time = match schema.format() {
+        Some(format) => parse_with_format(s, format)
+            .and_then(|x| {
+                let time = x.to_naive_time().map_err(|e| e.to_string())?;
+                let offset = x.offset().and_then(FixedOffset::east_opt);
+                Ok(TimeOfDay { time, offset })
+            })
+            .map_err(|e| format!("unable to parse as 'time' ({}): {}", format, e)),
+        None => parse_time(s).map_err(str::to_string),
     };
 
-    let hour: usize = (&captures[1]).parse().expect("invalid regex");
-    let min: usize = (&captures[2]).parse().expect("invalid regex");
-    let sec: usize = (&captures[3]).parse().expect("invalid regex");
+    let time = match time {
+        Ok(x) => x,
+        Err(e) => return scope.error("type", e).into(),
+    };
 
-    if (hour <= 23 && min <= 59 && sec <= 59) || (hour == 23 && min == 59 && sec == 60) {
-        ValidationState::new()
-    } else {
-        scope.error("type", "invalid `time` range").into()
+    if time.offset.is_none() && schema.timezone_required() {
+        return scope.error("timezoneRequired", "expected time zone offset").into();
     }
+
+    validate_time_limits(scope, &time)
 }
@@ -0,0 +1,35 @@
+use serde_json::Value;
+
+use crate::{
+    parser::duration::Duration,
+    schema::Limit,
+    validator::{
+        scope::ScopedSchema,
+        state::ValidationState,
+        types::{validate_as_string, validate_limits},
+    },
+};
+
+// `min` & `max` are durations (`30s`) or numbers of seconds
+
+fn parse_limit(limit: &Limit) -> Result<Duration, String> {
+    match limit {
+        Limit::String(s) => s.parse(),
+        Limit::Number(n) => Duration::from_secs_f64(n.as_f64().unwrap_or(-1.0)),
+    }
+    .map_err(|e| e.message().to_string())
+}
+
+pub fn validate_as_duration(scope: &ScopedSchema, data: &Value) -> ValidationState {
+    let mut state = validate_as_string(scope, data);
+    if !state.is_valid() {
+        return state;
+    }
+
+    match data.as_str().expect("invalid validate_as_string").parse::<Duration>() {
+        Ok(duration) => state.extend(validate_limits(scope, &duration, parse_limit)),
+        Err(e) => state.push_error(scope.error("type", format!("expected 'duration': {}", e.message()))),
+    };
+
+    state
+}
@@ -1,36 +1,217 @@
+use base64::Engine;
 use lazy_static::lazy_static;
 use regex::Regex;
 use serde_json::Value;
 
-use crate::validator::{scope::ScopedSchema, state::ValidationState};
+use crate::{
+    utils::{
+        image::{self, ImageInfo},
+        mime,
+    },
+    validator::{scope::ScopedSchema, state::ValidationState},
+};
 
 lazy_static! {
     // data:text/plain;name=test.txt;base64,aGV...
     static ref FILE_REGEX: Regex =
-        Regex::new(r"^data:.*;name=(.*);([a-zA-Z0-9]+),(.*)$").unwrap();
+        Regex::new(r"^data:([^;,]*).*;name=(.*);([a-zA-Z0-9]+),(.*)$").unwrap();
 }
 
-pub fn validate_as_file(scope: &ScopedSchema, data: &Value) -> ValidationState {
-    let s = match data.as_str() {
-        Some(x) => x,
-        None => return scope.error("type", "expected `file`").into(),
-    };
+// https://tools.ietf.org/html/rfc2397#section-2
+const DEFAULT_MEDIA_TYPE: &str = "text/plain";
+
+/// Checks that the content matches media types implied by the name extension & declared media type
+fn validate_magic(scope: &ScopedSchema, media_type: &str, name: &str, content: &[u8]) -> ValidationState {
+    let mut state = ValidationState::new();
+    let sniffed = mime::sniff(content);
+
+    let mut expected = vec![];
+    if mime::is_detectable(media_type) {
+        expected.push(media_type.to_lowercase());
+    }
+    if let Some(x) = mime::media_type_for_name(name) {
+        if !expected.iter().any(|e| e == x) {
+            expected.push(x.to_string());
+        }
+    }
+
+    for expected in expected {
+        match sniffed {
+            Some(sniffed) if sniffed == expected => {}
+            Some(sniffed) => state.push_error(scope.error(
+                "type",
+                format!("file content is '{}', expected '{}'", sniffed, expected),
+            )),
+            None => state.push_error(scope.error("type", format!("file content is not '{}'", expected))),
+        };
+    }
+
+    state
+}
+
+fn validate_keywords(scope: &ScopedSchema, media_type: &str, name: &str, content: &[u8]) -> ValidationState {
+    let schema = scope.schema();
+    let mut state = ValidationState::new();
+
+    let media_types = schema.media_types();
+    if !media_types.is_empty() && !media_types.iter().any(|x| mime::matches(media_type, x)) {
+        state.push_error(scope.error(
+            "mediaTypes",
+            format!(
+                "unexpected media type '{}', expected one of '{}'",
+                media_type,
+                media_types.join("', '")
+            ),
+        ));
+    }
+
+    if let Some(min) = schema.min_size() {
+        if content.len() < min {
+            state.push_error(scope.error("minSize", format!("expected '>= {}' bytes", min)));
+        }
+    }
+
+    if let Some(max) = schema.max_size() {
+        if content.len() > max {
+            state.push_error(scope.error("maxSize", format!("expected '<= {}' bytes", max)));
+        }
+    }
+
+    if let Some(regex) = schema.name_pattern() {
+        if !regex.is_match(name) {
+            state.push_error(scope.error("namePattern", "file name does not match"));
+        }
+    }
+
+    state
+}
+
+#[derive(Clone, Copy)]
+enum Dimension {
+    Width,
+    Height,
+}
+
+impl Dimension {
+    fn name(self) -> &'static str {
+        match self {
+            Dimension::Width => "width",
+            Dimension::Height => "height",
+        }
+    }
+
+    fn value(self, info: &ImageInfo) -> u32 {
+        match self {
+            Dimension::Width => info.width,
+            Dimension::Height => info.height,
+        }
+    }
+}
+
+type Comparison = fn(&u32, &u32) -> bool;
+
+/// Validates image keywords, the image header is parsed only if there's an image keyword
+fn validate_image(scope: &ScopedSchema, content: &[u8]) -> ValidationState {
+    let schema = scope.schema();
+
+    // keyword, expected value, dimension, operator & comparison
+    let dimensions: [(&str, Option<u32>, Dimension, &str, Comparison); 6] = [
+        ("width", schema.width(), Dimension::Width, "==", u32::eq),
+        ("height", schema.height(), Dimension::Height, "==", u32::eq),
+        ("maxWidth", schema.max_width(), Dimension::Width, "<=", u32::le),
+        ("maxHeight", schema.max_height(), Dimension::Height, "<=", u32::le),
+        ("minWidth", schema.min_width(), Dimension::Width, ">=", u32::ge),
+        ("minHeight", schema.min_height(), Dimension::Height, ">=", u32::ge),
+    ];
 
-    let captures = match FILE_REGEX.captures(s) {
+    if schema.image_format().is_none() && dimensions.iter().all(|(_, x, ..)| x.is_none()) {
+        return ValidationState::new();
+    }
+
+    let info = match image::image_info(content) {
         Some(x) => x,
-        _ => return scope.error("type", "expected `file`").into(),
+        None => return scope.error("type", "unable to read image header").into(),
     };
 
-    if (&captures[1]).is_empty() {
-        return scope.error("type", "file name is missing").into();
+    let mut state = ValidationState::new();
+
+    if let Some(format) = schema.image_format() {
+        if !format.eq_ignore_ascii_case(info.format) {
+            state.push_error(scope.error(
+                "imageFormat",
+                format!("expected '{}' image, got '{}'", format, info.format),
+            ));
+        }
     }
 
-    if &captures[2] != "base64" {
-        return scope.error("type", "only base64 is supported").into();
+    for (keyword, expected, dimension, op, compare) in dimensions.iter() {
+        let expected = match expected {
+            Some(x) => x,
+            None => continue,
+        };
+
+        let actual = dimension.value(&info);
+
+        if !compare(&actual, expected) {
+            state.push_error(scope.error(
+                *keyword,
+                format!("expected {} '{} {}', got '{}'", dimension.name(), op, expected, actual),
+            ));
+        }
+    }
+
+    state
+}
+
+/// Decoded `file` value
+pub(crate) struct File<'a> {
+    pub media_type: &'a str,
+    pub name: &'a str,
+    pub content: Vec<u8>,
+}
+
+/// Parses & decodes `file` value, returns error message on failure
+pub(crate) fn parse_file(s: &str) -> Result<File<'_>, &'static str> {
+    let captures = FILE_REGEX.captures(s).ok_or("expected `file`")?;
+
+    let media_type = match captures.get(1).expect("invalid regex").as_str() {
+        "" => DEFAULT_MEDIA_TYPE,
+        x => x,
+    };
+    let name = captures.get(2).expect("invalid regex").as_str();
+
+    if name.is_empty() {
+        return Err("file name is missing");
     }
 
-    match base64::decode(&captures[3]) {
-        Ok(_) => ValidationState::new(),
-        Err(_) => scope.error("type", "unable to decode file data").into(),
+    if &captures[3] != "base64" {
+        return Err("only base64 is supported");
     }
+
+    let content = base64::engine::general_purpose::STANDARD
+        .decode(&captures[4])
+        .map_err(|_| "unable to decode file data")?;
+
+    Ok(File {
+        media_type,
+        name,
+        content,
+    })
+}
+
+pub fn validate_as_file(scope: &ScopedSchema, data: &Value) -> ValidationState {
+    let s = match data.as_str() {
+        Some(x) => x,
+        None => return scope.error("type", "expected `file`").into(),
+    };
+
+    let file = match parse_file(s) {
+        Ok(x) => x,
+        Err(e) => return scope.error("type", e).into(),
+    };
+
+    let mut state = validate_magic(scope, file.media_type, file.name, &file.content);
+    state.extend(validate_keywords(scope, file.media_type, file.name, &file.content));
+    state.extend(validate_image(scope, &file.content));
+    state
 }
@@ -0,0 +1,42 @@
+use serde_json::Value;
+
+use crate::validator::{scope::ScopedSchema, state::ValidationState, types::validate_as_string};
+
+pub fn validate_as_hex(scope: &ScopedSchema, data: &Value) -> ValidationState {
+    let mut state = validate_as_string(scope, data);
+    if !state.is_valid() {
+        return state;
+    }
+
+    let hex = data.as_str().expect("invalid validate_as_string");
+
+    if let Some((idx, c)) = hex.chars().enumerate().find(|(_, c)| !c.is_ascii_hexdigit()) {
+        state.push_error(scope.error(
+            "type",
+            format!("expected 'hex': invalid digit '{}' at position {}", c, idx + 1),
+        ));
+        return state;
+    }
+
+    if !hex.len().is_multiple_of(2) {
+        state.push_error(scope.error("type", "expected 'hex': odd number of digits"));
+        return state;
+    }
+
+    let schema = scope.schema();
+    let bytes = hex.len() / 2;
+
+    if let Some(min) = schema.min_bytes() {
+        if bytes < min {
+            state.push_error(scope.error("minBytes", format!("expected '>= {}' bytes, got {}", min, bytes)));
+        }
+    }
+
+    if let Some(max) = schema.max_bytes() {
+        if bytes > max {
+            state.push_error(scope.error("maxBytes", format!("expected '<= {}' bytes, got {}", max, bytes)));
+        }
+    }
+
+    state
+}
@@ -0,0 +1,79 @@
+use std::net::IpAddr;
+
+use serde_json::Value;
+
+use crate::{
+    utils::net::is_valid_hostname,
+    validator::{scope::ScopedSchema, state::ValidationState},
+};
+
+// Single /etc/hosts entry
+//
+//   address: 127.0.0.1
+//   hostname: localhost
+//   aliases:
+//     - localhost.localdomain
+//
+// address must be an ipv4 or ipv6, hostname & aliases must be hostnames, aliases are optional.
+// Schema keywords (`pattern`, `inNetwork`, ...) don't apply to subfields.
+
+const ADDRESS_KEY: &str = "address";
+const HOSTNAME_KEY: &str = "hostname";
+const ALIASES_KEY: &str = "aliases";
+
+fn validate_hostname(scope: &ScopedSchema, data: &Value) -> ValidationState {
+    match data.as_str() {
+        Some(x) if is_valid_hostname(x) => ValidationState::new(),
+        _ => scope.error("type", "expected 'hostname'").into(),
+    }
+}
+
+pub fn validate_as_host_entry(scope: &ScopedSchema, data: &Value) -> ValidationState {
+    let object = match data.as_object() {
+        Some(x) => x,
+        None => return scope.error("type", "expected 'host-entry'").into(),
+    };
+
+    let mut state = ValidationState::new();
+
+    for key in object.keys() {
+        if key != ADDRESS_KEY && key != HOSTNAME_KEY && key != ALIASES_KEY {
+            state.push_error(scope.error("type", format!("unexpected '{}' in 'host-entry'", key)));
+        }
+    }
+
+    match object.get(ADDRESS_KEY) {
+        Some(address) => {
+            let scope = scope.scope_with_data_property(ADDRESS_KEY);
+            if address.as_str().and_then(|x| x.parse::<IpAddr>().ok()).is_none() {
+                state.push_error(scope.error("type", "expected 'ipv4' or 'ipv6'"));
+            }
+        }
+        None => state.push_error(scope.error("type", "missing 'address'")),
+    };
+
+    match object.get(HOSTNAME_KEY) {
+        Some(hostname) => state.extend(validate_hostname(
+            &scope.scope_with_data_property(HOSTNAME_KEY),
+            hostname,
+        )),
+        None => state.push_error(scope.error("type", "missing 'hostname'")),
+    };
+
+    match object.get(ALIASES_KEY) {
+        Some(Value::Array(aliases)) => {
+            let scope = scope.scope_with_data_property(ALIASES_KEY);
+            for (idx, alias) in aliases.iter().enumerate() {
+                state.extend(validate_hostname(&scope.scope_with_data_index(idx), alias));
+            }
+        }
+        Some(_) => state.push_error(
+            scope
+                .scope_with_data_property(ALIASES_KEY)
+                .error("type", "expected 'array'"),
+        ),
+        None => {}
+    };
+
+    state
+}
@@ -0,0 +1,19 @@
+use serde_json::Value;
+
+use crate::{
+    utils::net::is_valid_interface_name,
+    validator::{scope::ScopedSchema, state::ValidationState, types::validate_as_string},
+};
+
+pub fn validate_as_interface_name(scope: &ScopedSchema, data: &Value) -> ValidationState {
+    let mut state = validate_as_string(scope, data);
+    if !state.is_valid() {
+        return state;
+    }
+
+    if !is_valid_interface_name(data.as_str().expect("invalid validate_as_string")) {
+        state.push_error(scope.error("type", "expected 'interface-name'"));
+    }
+
+    state
+}
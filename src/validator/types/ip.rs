@@ -1,8 +1,70 @@
-use std::net::IpAddr;
+use std::net::{IpAddr, Ipv4Addr};
 
+use balena_temen as temen;
 use serde_json::Value;
 
-use crate::validator::{scope::ScopedSchema, state::ValidationState, types::validate_as_string};
+use crate::{
+    error::Error,
+    parser::cidr::{is_multicast_address, is_private_address, is_reserved_address, Cidr},
+    schema::InNetwork,
+    validator::{scope::ScopedSchema, state::ValidationState, types::validate_as_string},
+};
+
+fn is_unicast(ip: &IpAddr) -> bool {
+    !ip.is_unspecified() && !is_multicast_address(ip) && *ip != IpAddr::V4(Ipv4Addr::BROADCAST)
+}
+
+/// Returns `inNetwork` network, formula is evaluated against the root data
+fn in_network(scope: &ScopedSchema, in_network: &InNetwork) -> Result<Cidr, String> {
+    let formula = match in_network {
+        InNetwork::Cidr(cidr) => return Ok(*cidr),
+        InNetwork::Formula(formula) => formula,
+    };
+
+    let root = scope.root().ok_or("missing data to evaluate formula")?;
+
+    let engine = temen::Engine::default();
+    let mut ctx = temen::Context::default();
+    let position = scope.data_path().to_identifier();
+
+    let network = engine
+        .eval(formula, &position, root, &mut ctx)
+        .map_err(|_| format!("unable to evaluate formula '{}'", formula))?;
+
+    network
+        .as_str()
+        .ok_or_else(|| format!("formula '{}' does not evaluate to a string", formula))?
+        .parse()
+        .map_err(|e: Error| format!("formula '{}': {}", formula, e.message()))
+}
+
+/// Validates `inNetwork`, `notReserved`, `privateOnly` & `unicastOnly` keywords
+fn validate_keywords(scope: &ScopedSchema, ip: &IpAddr) -> ValidationState {
+    let schema = scope.schema();
+    let mut state = ValidationState::new();
+
+    match schema.in_network().map(|x| in_network(scope, x)) {
+        Some(Ok(cidr)) if !cidr.contains(ip) => {
+            state.push_error(scope.error("inNetwork", format!("address is not in the '{}' network", cidr)))
+        }
+        Some(Err(e)) => state.push_error(scope.error("inNetwork", format!("invalid 'inNetwork' value: {}", e))),
+        _ => {}
+    };
+
+    if schema.not_reserved() && is_reserved_address(ip) {
+        state.push_error(scope.error("notReserved", "reserved address is not allowed"));
+    }
+
+    if schema.private_only() && !is_private_address(ip) {
+        state.push_error(scope.error("privateOnly", "expected private address"));
+    }
+
+    if schema.unicast_only() && !is_unicast(ip) {
+        state.push_error(scope.error("unicastOnly", "expected unicast address"));
+    }
+
+    state
+}
 
 pub fn validate_as_ipv4(scope: &ScopedSchema, data: &Value) -> ValidationState {
     let mut state = validate_as_string(scope, data);
@@ -15,7 +77,7 @@ pub fn validate_as_ipv4(scope: &ScopedSchema, data: &Value) -> ValidationState {
     match ip {
         Ok(x) if !x.is_ipv4() => state.push_error(scope.error("type", "valid IP address, but not an 'ipv4'")),
         Err(_) => state.push_error(scope.error("type", "unable to parse as 'ipv4'")),
-        _ => {}
+        Ok(x) => state.extend(validate_keywords(scope, &x)),
     };
 
     state
@@ -32,7 +94,7 @@ pub fn validate_as_ipv6(scope: &ScopedSchema, data: &Value) -> ValidationState {
     match ip {
         Ok(x) if !x.is_ipv6() => state.push_error(scope.error("type", "valid IP address, but not an 'ipv6'")),
         Err(_) => state.push_error(scope.error("type", "unable to parse as 'ipv6'")),
-        _ => {}
+        Ok(x) => state.extend(validate_keywords(scope, &x)),
     };
 
     state
@@ -1,9 +1,8 @@
 use serde_json::Value;
 
-use crate::validator::{
-    scope::ScopedSchema,
-    state::ValidationState,
-    types::{validate_as_ipv4, validate_as_ipv6},
+use crate::{
+    parser::iptables::Address,
+    validator::{scope::ScopedSchema, state::ValidationState, types::validate_as_string},
 };
 
 //
@@ -13,15 +12,38 @@ use crate::validator::{
 //          Destination specification.  See the description of the -s (source) flag for a detailed description
 //          of the syntax.  The flag --dst is an alias for this option.
 //
-// ip tables address is (ipv4|ipv6)[/mask]
+// ip tables address is (ipv4|ipv6)[/mask][,...]
 //
-// -d also accepts "ipv4/mask,ipv4/mask,...", but then, it should be stringlist of iptables-address
+// Every address in the list is validated separately. Address with host bits set (`10.0.0.1/8`)
+// is valid, iptables clears them, but a warning is reported, because it's usually a typo.
 
 pub fn validate_as_iptables_address(scope: &ScopedSchema, data: &Value) -> ValidationState {
-    let state = validate_as_ipv4(scope, data);
-    if state.is_valid() {
+    let mut state = validate_as_string(scope, data);
+    if !state.is_valid() {
         return state;
     }
 
-    validate_as_ipv6(scope, data)
+    let s = data.as_str().expect("invalid validate_as_string");
+
+    for (idx, entry) in s.split(',').enumerate() {
+        let address = match entry.parse::<Address>() {
+            Ok(x) => x,
+            Err(e) => {
+                state.push_error(scope.error(
+                    "type",
+                    format!("expected 'iptables-address', address #{}: {}", idx + 1, e.message()),
+                ));
+                continue;
+            }
+        };
+
+        if address.has_host_bits() {
+            state.push_warning(scope.error(
+                "type",
+                format!("host bits are set in '{}', network is '{}'", address, address.network()),
+            ));
+        }
+    }
+
+    state
 }
@@ -0,0 +1,25 @@
+use serde_json::Value;
+
+use crate::{
+    tables::locale,
+    validator::{scope::ScopedSchema, state::ValidationState, types::validate_as_string},
+};
+
+pub fn validate_as_locale(scope: &ScopedSchema, data: &Value) -> ValidationState {
+    let mut state = validate_as_string(scope, data);
+    if !state.is_valid() {
+        return state;
+    }
+
+    let name = data.as_str().expect("invalid validate_as_string");
+
+    if !locale::is_valid_locale(name) {
+        let message = match locale::canonical_locale(name) {
+            Some(x) => format!("unknown locale '{}', did you mean '{}'?", name, x),
+            None => format!("unknown locale '{}'", name),
+        };
+        state.push_error(scope.error("type", message));
+    }
+
+    state
+}
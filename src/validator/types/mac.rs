@@ -0,0 +1,54 @@
+use serde_json::Value;
+
+use crate::{
+    parser::mac::MacAddress,
+    validator::{
+        scope::ScopedSchema,
+        state::ValidationState,
+        types::{validate_as_string, validate_flag},
+    },
+};
+
+// `private` means locally administered address, `multicast` is the individual/group bit.
+//
+// Addresses in other notations (`00-1A-2B-3C-4D-5E`) are valid, but there's a warning, because
+// the canonical format (`00:1a:2b:3c:4d:5e`) is expected in the configuration files.
+
+pub fn validate_as_mac_address(scope: &ScopedSchema, data: &Value) -> ValidationState {
+    let mut state = validate_as_string(scope, data);
+    if !state.is_valid() {
+        return state;
+    }
+
+    let s = data.as_str().expect("invalid validate_as_string");
+
+    let mac = match s.parse::<MacAddress>() {
+        Ok(x) => x,
+        Err(e) => {
+            return scope
+                .error("type", format!("expected 'mac-address': {}", e.message()))
+                .into()
+        }
+    };
+
+    if mac.to_string() != s {
+        state.push_warning(scope.error("type", format!("not in canonical format, expected '{}'", mac)));
+    }
+
+    let schema = scope.schema();
+    state.extend(validate_flag(
+        scope,
+        "private",
+        schema.private(),
+        mac.is_local(),
+        "locally administered address",
+    ));
+    state.extend(validate_flag(
+        scope,
+        "multicast",
+        schema.multicast(),
+        mac.is_multicast(),
+        "multicast address",
+    ));
+    state
+}
@@ -1,49 +1,86 @@
+pub use self::regex::validate_as_regex;
+pub use self::uuid::validate_as_uuid;
 pub use array::validate_as_array;
 pub use boolean::validate_as_boolean;
+pub use bytesize::validate_as_bytesize;
+pub use certificate::validate_as_certificate;
 pub use chrony::validate_as_chrony_address;
+pub use cidr::validate_as_cidr;
 pub use datetime::{validate_as_date, validate_as_datetime, validate_as_time};
 pub use dnsmasq::validate_as_dnsmasq_address;
+pub use duration::validate_as_duration;
 pub use email::validate_as_email;
+pub(crate) use file::parse_file;
 pub use file::validate_as_file;
+pub use hex::validate_as_hex;
 pub use hostname::validate_as_hostname;
+pub use hosts::validate_as_host_entry;
+pub use interface::validate_as_interface_name;
 pub use ip::{validate_as_ipv4, validate_as_ipv6};
 pub use iptables::validate_as_iptables_address;
-pub use number::{validate_as_integer, validate_as_number};
+pub use locale::validate_as_locale;
+pub use mac::validate_as_mac_address;
+pub use number::{
+    validate_as_int16, validate_as_int32, validate_as_int64, validate_as_int8, validate_as_integer, validate_as_number,
+    validate_as_uint16, validate_as_uint32, validate_as_uint64, validate_as_uint8,
+};
 pub use object::validate_as_object;
 pub use password::validate_as_password;
 pub use port::validate_as_port;
+pub use semver::validate_as_semver;
+pub use ssh::validate_as_ssh_public_key;
 pub use string::validate_as_string;
 pub use stringlist::validate_as_stringlist;
 pub use text::validate_as_text;
+pub use timezone::validate_as_timezone;
 pub use uri::validate_as_uri;
+pub use wifi::{validate_as_wifi_country, validate_as_wifi_psk, validate_as_wifi_ssid};
 
 mod array;
 mod boolean;
+mod bytesize;
+mod certificate;
 mod chrony;
+mod cidr;
 mod datetime;
 mod dnsmasq;
+mod duration;
 mod email;
 mod file;
+mod hex;
 mod hostname;
+mod hosts;
+mod interface;
 mod ip;
 mod iptables;
+mod locale;
+mod mac;
 mod number;
 mod object;
 mod password;
 mod port;
+mod regex;
+mod semver;
+mod ssh;
 mod string;
 mod stringlist;
 mod text;
+mod timezone;
 mod uri;
+mod uuid;
+mod wifi;
 
 //
 // Shared helpers
 //
 
-use regex::Regex;
+use ::regex::Regex;
 use serde_json::Value;
 
-use crate::validator::{scope::ScopedSchema, ValidationState};
+use crate::{
+    schema::Limit,
+    validator::{scope::ScopedSchema, ValidationState},
+};
 
 pub(crate) fn validate_as_string_with_regex(scope: &ScopedSchema, data: &Value, regex: &Regex) -> ValidationState {
     let mut state = validate_as_string(scope, data);
@@ -57,3 +94,65 @@ pub(crate) fn validate_as_string_with_regex(scope: &ScopedSchema, data: &Value,
 
     state
 }
+
+/// Validates boolean keyword (`private`, `multicast`, ...) against the value property
+///
+/// # Arguments
+///
+/// * `keyword` - A keyword name
+/// * `expected` - A keyword value, nothing is validated if it's not set
+/// * `actual` - A value property
+/// * `what` - A value description used in the error message (`multicast address`)
+pub(crate) fn validate_flag(
+    scope: &ScopedSchema,
+    keyword: &str,
+    expected: Option<bool>,
+    actual: bool,
+    what: &str,
+) -> ValidationState {
+    match expected {
+        Some(true) if !actual => scope.error(keyword, format!("expected {}", what)).into(),
+        Some(false) if actual => scope.error(keyword, format!("unexpected {}", what)).into(),
+        _ => ValidationState::new(),
+    }
+}
+
+type Comparison<T> = fn(&T, &T) -> bool;
+
+/// Validates `min` & `max` keywords of the types with their own units (`duration`, ...)
+///
+/// # Arguments
+///
+/// * `value` - A parsed value
+/// * `parse_limit` - A function which converts keyword value into the value type
+pub(crate) fn validate_limits<T, F>(scope: &ScopedSchema, value: &T, parse_limit: F) -> ValidationState
+where
+    T: PartialOrd + std::fmt::Display,
+    F: Fn(&Limit) -> Result<T, String>,
+{
+    let schema = scope.schema();
+    let mut state = ValidationState::new();
+
+    // keyword, limit, operator & comparison
+    let limits: [(&str, Option<&Limit>, &str, Comparison<T>); 2] = [
+        ("min", schema.min_limit(), ">=", T::ge),
+        ("max", schema.max_limit(), "<=", T::le),
+    ];
+
+    for (keyword, limit, op, compare) in &limits {
+        let limit = match limit.map(&parse_limit) {
+            Some(Ok(x)) => x,
+            Some(Err(e)) => {
+                state.push_error(scope.error(*keyword, format!("invalid '{}' value: {}", keyword, e)));
+                continue;
+            }
+            None => continue,
+        };
+
+        if !compare(value, &limit) {
+            state.push_error(scope.error(*keyword, format!("expected '{} {}', got '{}'", op, limit, value)));
+        }
+    }
+
+    state
+}
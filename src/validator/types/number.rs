@@ -1,7 +1,40 @@
 use serde_json::Value;
 use serde_yaml::Number;
 
-use crate::validator::{scope::ScopedSchema, state::ValidationState};
+use crate::{
+    schema::{Limit, Schema},
+    validator::{scope::ScopedSchema, state::ValidationState},
+};
+
+// Relative tolerance of the floating point step count (`0.3 / 0.1` is `2.9999999999999996`)
+const STEP_EPSILON: f64 = 1e-9;
+
+/// Checks that the distance from `min` is a whole number of steps
+trait Steps: Copy {
+    fn is_whole_steps(self, min: Self, step: Self) -> bool;
+}
+
+macro_rules! impl_integer_steps {
+    ($($t:ty),*) => {
+        $(
+            impl Steps for $t {
+                fn is_whole_steps(self, min: $t, step: $t) -> bool {
+                    // The distance doesn't fit into `$t` at the type limits
+                    (i128::from(self) - i128::from(min)) % i128::from(step) == 0
+                }
+            }
+        )*
+    };
+}
+
+impl_integer_steps!(u64, i64);
+
+impl Steps for f64 {
+    fn is_whole_steps(self, min: f64, step: f64) -> bool {
+        let steps = (self - min) / step;
+        (steps - steps.round()).abs() <= STEP_EPSILON * steps.abs().max(1.0)
+    }
+}
 
 fn validate_as<T, F1, F2>(
     scope: &ScopedSchema,
@@ -12,7 +45,7 @@ fn validate_as<T, F1, F2>(
 where
     F1: Fn(&Number) -> Option<T> + Copy,
     F2: Fn(&Value) -> Option<T> + Copy,
-    T: std::fmt::Display + std::cmp::PartialOrd + std::ops::Rem<Output = T> + Copy,
+    T: std::fmt::Display + std::cmp::PartialOrd + std::ops::Rem<Output = T> + std::ops::Sub<Output = T> + Steps + Copy,
 {
     let value = match data_value(data) {
         Some(x) => x,
@@ -27,6 +60,13 @@ where
     let schema = scope.schema();
     let mut state = ValidationState::new();
 
+    // Strings (`30s`, `512MiB`, ...) are accepted by the types with their own units only
+    for (keyword, limit) in &[("min", schema.min_limit()), ("max", schema.max_limit())] {
+        if let Some(Limit::String(_)) = limit {
+            state.push_error(scope.error(*keyword, format!("invalid '{}' value: expected number", keyword)));
+        }
+    }
+
     if let Some(min) = schema.min().and_then(schema_number_value) {
         if value < min {
             state.push_error(scope.error("min", format!("expected '>= {}", min)));
@@ -61,6 +101,22 @@ where
         }
     }
 
+    if let Some(step) = schema.step().and_then(schema_number_value).filter(|x| *x > zero) {
+        // Steps are counted from the `min`, value below the `min` is already reported
+        match schema.min().map(schema_number_value) {
+            Some(Some(min)) if value >= min && !value.is_whole_steps(min, step) => state.push_error(scope.error(
+                "step",
+                format!("expected '({} - {}) % {} == {}'", value, min, step, zero),
+            )),
+            // `min` can't be represented as `T` (negative `min` & `u64`), fallback type handles it
+            Some(None) => state.push_error(scope.error("step", "unable to count steps from 'min'")),
+            None if !value.is_whole_steps(zero, step) => {
+                state.push_error(scope.error("step", format!("expected '{} % {} == {}'", value, step, zero)))
+            }
+            _ => {}
+        };
+    }
+
     state
 }
 
@@ -73,11 +129,82 @@ pub fn validate_as_integer(scope: &ScopedSchema, data: &Value) -> ValidationStat
     validate_as(scope, data, Number::as_i64, Value::as_i64)
 }
 
+/// Returns `true` if any numeric keyword can't be represented as an integer (`step: 0.7`)
+fn has_fractional_keyword(schema: &Schema) -> bool {
+    let is_fractional = |x: &Number| x.as_u64().is_none() && x.as_i64().is_none();
+
+    [
+        schema.min(),
+        schema.max(),
+        schema.exclusive_min(),
+        schema.exclusive_max(),
+        schema.multiple_of(),
+        schema.step(),
+    ]
+    .iter()
+    .flatten()
+    .any(|x| is_fractional(x))
+}
+
 pub fn validate_as_number(scope: &ScopedSchema, data: &Value) -> ValidationState {
-    let state = validate_as_integer(scope, data);
-    if state.is_valid() {
-        return state;
+    // Integer validation skips fractional keywords, the floating point one handles them
+    if !has_fractional_keyword(scope.schema()) {
+        let state = validate_as_integer(scope, data);
+        if state.is_valid() {
+            return state;
+        }
     }
 
     validate_as(scope, data, Number::as_f64, Value::as_f64)
 }
+
+/// Validates integer which must fit into the `min..=max` range of the fixed width integer
+fn validate_as_fixed_width(scope: &ScopedSchema, data: &Value, min: i128, max: i128) -> ValidationState {
+    let mut state = validate_as_integer(scope, data);
+
+    if state.is_valid() {
+        let value = data
+            .as_i64()
+            .map(i128::from)
+            .or_else(|| data.as_u64().map(i128::from))
+            .expect("invalid validate_as_integer");
+
+        if value < min || value > max {
+            state.push_error(scope.error("type", format!("expected '{} in {}..={}'", value, min, max)));
+        }
+    }
+
+    state
+}
+
+pub fn validate_as_int8(scope: &ScopedSchema, data: &Value) -> ValidationState {
+    validate_as_fixed_width(scope, data, i8::MIN.into(), i8::MAX.into())
+}
+
+pub fn validate_as_int16(scope: &ScopedSchema, data: &Value) -> ValidationState {
+    validate_as_fixed_width(scope, data, i16::MIN.into(), i16::MAX.into())
+}
+
+pub fn validate_as_int32(scope: &ScopedSchema, data: &Value) -> ValidationState {
+    validate_as_fixed_width(scope, data, i32::MIN.into(), i32::MAX.into())
+}
+
+pub fn validate_as_int64(scope: &ScopedSchema, data: &Value) -> ValidationState {
+    validate_as_fixed_width(scope, data, i64::MIN.into(), i64::MAX.into())
+}
+
+pub fn validate_as_uint8(scope: &ScopedSchema, data: &Value) -> ValidationState {
+    validate_as_fixed_width(scope, data, u8::MIN.into(), u8::MAX.into())
+}
+
+pub fn validate_as_uint16(scope: &ScopedSchema, data: &Value) -> ValidationState {
+    validate_as_fixed_width(scope, data, u16::MIN.into(), u16::MAX.into())
+}
+
+pub fn validate_as_uint32(scope: &ScopedSchema, data: &Value) -> ValidationState {
+    validate_as_fixed_width(scope, data, u32::MIN.into(), u32::MAX.into())
+}
+
+pub fn validate_as_uint64(scope: &ScopedSchema, data: &Value) -> ValidationState {
+    validate_as_fixed_width(scope, data, u64::MIN.into(), u64::MAX.into())
+}
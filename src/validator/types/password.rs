@@ -1,7 +1,105 @@
+//! Password validation
+//!
+//! Messages must never contain the password (or any part of it), they're never formatted
+//! with the value. Nested `contentSchema` errors are replaced with a single error as well.
 use serde_json::Value;
 
-use crate::validator::{scope::ScopedSchema, state::ValidationState, types::validate_as_string};
+use crate::{
+    tables::dictionary,
+    validator::{scope::ScopedSchema, state::ValidationState, types::validate_as_string},
+};
+
+// Character pool sizes used by the entropy estimate
+const LOWERCASE_POOL: u32 = 26;
+const UPPERCASE_POOL: u32 = 26;
+const DIGITS_POOL: u32 = 10;
+const SYMBOLS_POOL: u32 = 33;
+// Non ASCII letters, rough guess
+const OTHER_POOL: u32 = 100;
+
+/// Character class membership test
+type CharClass = fn(char) -> bool;
+
+fn is_symbol(c: char) -> bool {
+    !c.is_alphanumeric()
+}
+
+/// Estimated entropy in bits
+///
+/// Naive estimate, number of characters multiplied by bits per character of the pool formed
+/// by the used character classes. Dictionary words & patterns are not taken into account,
+/// use the `noDictionaryWords` keyword for them.
+fn entropy(password: &str) -> f64 {
+    let classes: [(CharClass, u32); 5] = [
+        (|c| c.is_ascii_lowercase(), LOWERCASE_POOL),
+        (|c| c.is_ascii_uppercase(), UPPERCASE_POOL),
+        (|c| c.is_ascii_digit(), DIGITS_POOL),
+        (|c| c.is_ascii() && is_symbol(c), SYMBOLS_POOL),
+        (|c| !c.is_ascii(), OTHER_POOL),
+    ];
+
+    let pool: u32 = classes
+        .iter()
+        .filter(|(is_member, _)| password.chars().any(is_member))
+        .map(|(_, size)| size)
+        .sum();
+
+    if pool == 0 {
+        return 0.0;
+    }
+
+    password.chars().count() as f64 * f64::from(pool).log2()
+}
 
 pub fn validate_as_password(scope: &ScopedSchema, data: &Value) -> ValidationState {
-    validate_as_string(scope, data)
+    let mut state = validate_as_string(scope, data);
+    if !state.is_valid() {
+        return state;
+    }
+
+    let password = data.as_str().expect("invalid validate_as_string");
+    let schema = scope.schema();
+
+    let classes: [(&str, Option<usize>, CharClass, &str); 4] = [
+        (
+            "minLowercase",
+            schema.min_lowercase(),
+            char::is_lowercase,
+            "lowercase letter(s)",
+        ),
+        (
+            "minUppercase",
+            schema.min_uppercase(),
+            char::is_uppercase,
+            "uppercase letter(s)",
+        ),
+        ("minDigits", schema.min_digits(), |c| c.is_ascii_digit(), "digit(s)"),
+        ("minSymbols", schema.min_symbols(), is_symbol, "symbol(s)"),
+    ];
+
+    for (keyword, min, is_member, what) in classes.iter() {
+        if let Some(min) = min {
+            if password.chars().filter(|c| is_member(*c)).count() < *min {
+                state.push_error(scope.error(*keyword, format!("expected at least {} {}", min, what)));
+            }
+        }
+    }
+
+    if let Some(min) = schema.min_entropy() {
+        if entropy(password) < min {
+            state.push_error(scope.error(
+                "minEntropy",
+                format!(
+                    "expected estimated entropy '>= {}' bits, use longer password or more character classes",
+                    min
+                ),
+            ));
+        }
+    }
+
+    if schema.no_dictionary_words() && dictionary::contains_word(password) {
+        state.push_error(scope.error("noDictionaryWords", "contains common password or dictionary word"));
+    }
+
+    state
 }
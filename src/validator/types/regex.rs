@@ -0,0 +1,18 @@
+use regex::Regex;
+use serde_json::Value;
+
+use crate::validator::{scope::ScopedSchema, state::ValidationState, types::validate_as_string};
+
+pub fn validate_as_regex(scope: &ScopedSchema, data: &Value) -> ValidationState {
+    let mut state = validate_as_string(scope, data);
+    if !state.is_valid() {
+        return state;
+    }
+
+    // The compile error contains the pattern with the position of the error marked
+    if let Err(e) = Regex::new(data.as_str().expect("invalid validate_as_string")) {
+        state.push_error(scope.error("type", format!("expected 'regex': {}", e)));
+    }
+
+    state
+}
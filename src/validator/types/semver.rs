@@ -0,0 +1,32 @@
+use serde_json::Value;
+
+use crate::{
+    parser::semver::Version,
+    schema::Limit,
+    validator::{
+        scope::ScopedSchema,
+        state::ValidationState,
+        types::{validate_as_string, validate_limits},
+    },
+};
+
+fn parse_limit(limit: &Limit) -> Result<Version, String> {
+    match limit {
+        Limit::String(s) => s.parse().map_err(|e: crate::error::Error| e.message().to_string()),
+        Limit::Number(_) => Err("expected version string".to_string()),
+    }
+}
+
+pub fn validate_as_semver(scope: &ScopedSchema, data: &Value) -> ValidationState {
+    let mut state = validate_as_string(scope, data);
+    if !state.is_valid() {
+        return state;
+    }
+
+    match data.as_str().expect("invalid validate_as_string").parse::<Version>() {
+        Ok(version) => state.extend(validate_limits(scope, &version, parse_limit)),
+        Err(e) => state.push_error(scope.error("type", format!("expected 'semver': {}", e.message()))),
+    };
+
+    state
+}
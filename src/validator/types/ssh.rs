@@ -0,0 +1,28 @@
+use serde_json::Value;
+
+use crate::{
+    utils::ssh::parse_public_key,
+    validator::{scope::ScopedSchema, state::ValidationState, types::validate_as_string},
+};
+
+// SSH public key (single line of the id_*.pub file)
+//
+//   ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIGjhy/7vGVEQWpW8VA6XdeW9pJ9pv6TaX7VyDGx8tHUV user@host
+//
+// Key type must be one of the supported algorithms, key data must be a base64 encoded blob
+// with the same key type embedded, followed by the key in the algorithm wire format (curve
+// point, ed25519 key, RSA exponent & modulus) & nothing else. Comment is optional.
+
+pub fn validate_as_ssh_public_key(scope: &ScopedSchema, data: &Value) -> ValidationState {
+    let mut state = validate_as_string(scope, data);
+
+    if !state.is_valid() {
+        return state;
+    }
+
+    if let Err(e) = parse_public_key(data.as_str().expect("invalid validate_as_string")) {
+        state.push_error(scope.error("type", format!("expected 'ssh-public-key': {}", e)));
+    }
+
+    state
+}
@@ -1,7 +1,60 @@
 use serde_json::Value;
 
-use crate::validator::{scope::ScopedSchema, state::ValidationState, types::validate_as_array};
+use crate::{
+    parser::stringlist::{is_joinable, StringList, DEFAULT_SEPARATOR},
+    validator::{scope::ScopedSchema, state::ValidationState, types::validate_as_array},
+};
 
 pub fn validate_as_stringlist(scope: &ScopedSchema, data: &Value) -> ValidationState {
-    validate_as_array(scope, data)
+    let separator = scope.schema().separator().unwrap_or(DEFAULT_SEPARATOR);
+
+    if separator.is_empty() {
+        return scope.error("separator", "separator must not be empty").into();
+    }
+
+    match data {
+        // Separated string is validated as if it was an array, errors point to the item index
+        Value::String(s) => match StringList::parse(s, separator) {
+            Ok(list) => {
+                let items = Value::Array(list.into_items().into_iter().map(Value::String).collect());
+                validate_as_array(scope, &items)
+            }
+            Err(e) => scope.error("type", e.message()).into(),
+        },
+        Value::Array(items) => {
+            let mut state = ValidationState::new();
+
+            for (idx, item) in items.iter().enumerate() {
+                match item.as_str() {
+                    None => state.push_error(scope.scope_with_data_index(idx).error("type", "expected 'string'")),
+                    // Must be representable as a separated string as well
+                    Some(x) if !is_joinable(x, separator) => state.push_error(
+                        scope
+                            .scope_with_data_index(idx)
+                            .error("separator", format!("item contains the separator '{}'", separator)),
+                    ),
+                    Some(_) => {}
+                };
+            }
+
+            if !state.is_valid() {
+                return state;
+            }
+
+            // Items can form the separator across their boundaries
+            let list = StringList::from(
+                items
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .map(str::to_string)
+                    .collect::<Vec<_>>(),
+            );
+            if let Err(e) = list.join(separator) {
+                return scope.error("separator", e.message()).into();
+            }
+
+            validate_as_array(scope, data)
+        }
+        _ => scope.error("type", "expected 'array' or 'string'").into(),
+    }
 }
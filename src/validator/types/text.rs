@@ -3,5 +3,40 @@ use serde_json::Value;
 use crate::validator::{scope::ScopedSchema, state::ValidationState, types::validate_as_string};
 
 pub fn validate_as_text(scope: &ScopedSchema, data: &Value) -> ValidationState {
-    validate_as_string(scope, data)
+    let mut state = validate_as_string(scope, data);
+    if !state.is_valid() {
+        return state;
+    }
+
+    let schema = scope.schema();
+    // Trailing new line doesn't start a new line, `\r\n` is handled as well
+    let lines: Vec<&str> = data.as_str().expect("invalid validate_as_string").lines().collect();
+
+    if let Some(min) = schema.min_lines() {
+        if lines.len() < min {
+            state.push_error(scope.error("minLines", format!("expected '>= {}' lines, got {}", min, lines.len())));
+        }
+    }
+
+    if let Some(max) = schema.max_lines() {
+        if lines.len() > max {
+            state.push_error(scope.error("maxLines", format!("expected '<= {}' lines, got {}", max, lines.len())));
+        }
+    }
+
+    for (idx, line) in lines.iter().enumerate() {
+        if let Some(regex) = schema.line_pattern() {
+            if !regex.is_match(line) {
+                state.push_error(scope.error("linePattern", format!("line {} does not match", idx + 1)));
+            }
+        }
+
+        if let Some(regex) = schema.forbid_pattern() {
+            if regex.is_match(line) {
+                state.push_error(scope.error("forbidPattern", format!("line {} matches forbidden pattern", idx + 1)));
+            }
+        }
+    }
+
+    state
 }
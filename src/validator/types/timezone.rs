@@ -0,0 +1,25 @@
+use serde_json::Value;
+
+use crate::{
+    tables::timezone,
+    validator::{scope::ScopedSchema, state::ValidationState, types::validate_as_string},
+};
+
+pub fn validate_as_timezone(scope: &ScopedSchema, data: &Value) -> ValidationState {
+    let mut state = validate_as_string(scope, data);
+    if !state.is_valid() {
+        return state;
+    }
+
+    let name = data.as_str().expect("invalid validate_as_string");
+
+    if !timezone::is_valid_timezone(name) {
+        let message = match timezone::suggest_timezone(name) {
+            Some(x) => format!("unknown timezone '{}', did you mean '{}'?", name, x),
+            None => format!("unknown timezone '{}'", name),
+        };
+        state.push_error(scope.error("type", message));
+    }
+
+    state
+}
@@ -0,0 +1,32 @@
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::validator::{scope::ScopedSchema, state::ValidationState, types::validate_as_string};
+
+pub fn validate_as_uuid(scope: &ScopedSchema, data: &Value) -> ValidationState {
+    let mut state = validate_as_string(scope, data);
+    if !state.is_valid() {
+        return state;
+    }
+
+    let uuid = match Uuid::parse_str(data.as_str().expect("invalid validate_as_string")) {
+        Ok(x) => x,
+        Err(e) => {
+            state.push_error(scope.error("type", format!("expected 'uuid': {}", e)));
+            return state;
+        }
+    };
+
+    if let Some(version) = scope.schema().uuid_version() {
+        let actual = uuid.get_version_num();
+
+        if actual != usize::from(version) {
+            state.push_error(scope.error(
+                "uuidVersion",
+                format!("expected UUID version {}, got version {}", version, actual),
+            ));
+        }
+    }
+
+    state
+}
@@ -0,0 +1,114 @@
+use serde_json::Value;
+
+use crate::validator::{scope::ScopedSchema, state::ValidationState, types::validate_as_string};
+
+// IEEE 802.11, SSID is 0..32 octets, but the empty (wildcard) SSID can't be configured
+const MAX_SSID_LEN: usize = 32;
+
+// IEEE 802.11i, passphrase is 8..63 printable ASCII characters or 64 hexadecimal digits
+const MIN_PASSPHRASE_LEN: usize = 8;
+const MAX_PASSPHRASE_LEN: usize = 63;
+const HEX_PSK_LEN: usize = 64;
+
+// ISO 3166-1 alpha-2 codes, plus the `00` (world) regulatory domain
+const COUNTRIES: &[&str] = &[
+    "00", "AD", "AE", "AF", "AG", "AI", "AL", "AM", "AO", "AQ", "AR", "AS", "AT", "AU", "AW", "AX", "AZ", "BA", "BB",
+    "BD", "BE", "BF", "BG", "BH", "BI", "BJ", "BL", "BM", "BN", "BO", "BQ", "BR", "BS", "BT", "BV", "BW", "BY", "BZ",
+    "CA", "CC", "CD", "CF", "CG", "CH", "CI", "CK", "CL", "CM", "CN", "CO", "CR", "CU", "CV", "CW", "CX", "CY", "CZ",
+    "DE", "DJ", "DK", "DM", "DO", "DZ", "EC", "EE", "EG", "EH", "ER", "ES", "ET", "FI", "FJ", "FK", "FM", "FO", "FR",
+    "GA", "GB", "GD", "GE", "GF", "GG", "GH", "GI", "GL", "GM", "GN", "GP", "GQ", "GR", "GS", "GT", "GU", "GW", "GY",
+    "HK", "HM", "HN", "HR", "HT", "HU", "ID", "IE", "IL", "IM", "IN", "IO", "IQ", "IR", "IS", "IT", "JE", "JM", "JO",
+    "JP", "KE", "KG", "KH", "KI", "KM", "KN", "KP", "KR", "KW", "KY", "KZ", "LA", "LB", "LC", "LI", "LK", "LR", "LS",
+    "LT", "LU", "LV", "LY", "MA", "MC", "MD", "ME", "MF", "MG", "MH", "MK", "ML", "MM", "MN", "MO", "MP", "MQ", "MR",
+    "MS", "MT", "MU", "MV", "MW", "MX", "MY", "MZ", "NA", "NC", "NE", "NF", "NG", "NI", "NL", "NO", "NP", "NR", "NU",
+    "NZ", "OM", "PA", "PE", "PF", "PG", "PH", "PK", "PL", "PM", "PN", "PR", "PS", "PT", "PW", "PY", "QA", "RE", "RO",
+    "RS", "RU", "RW", "SA", "SB", "SC", "SD", "SE", "SG", "SH", "SI", "SJ", "SK", "SL", "SM", "SN", "SO", "SR", "SS",
+    "ST", "SV", "SX", "SY", "SZ", "TC", "TD", "TF", "TG", "TH", "TJ", "TK", "TL", "TM", "TN", "TO", "TR", "TT", "TV",
+    "TW", "TZ", "UA", "UG", "UM", "US", "UY", "UZ", "VA", "VC", "VE", "VG", "VI", "VN", "VU", "WF", "WS", "YE", "YT",
+    "ZA", "ZM", "ZW",
+];
+
+pub fn validate_as_wifi_ssid(scope: &ScopedSchema, data: &Value) -> ValidationState {
+    let mut state = validate_as_string(scope, data);
+    if !state.is_valid() {
+        return state;
+    }
+
+    let ssid = data.as_str().expect("invalid validate_as_string");
+
+    if ssid.is_empty() {
+        state.push_error(scope.error("type", "SSID must not be empty"));
+    } else if ssid.len() > MAX_SSID_LEN {
+        // Multibyte characters are the usual surprise, mention both lengths
+        state.push_error(scope.error(
+            "type",
+            format!(
+                "SSID must not be longer than {} bytes, got {} bytes ({} characters)",
+                MAX_SSID_LEN,
+                ssid.len(),
+                ssid.chars().count()
+            ),
+        ));
+    }
+
+    state
+}
+
+pub fn validate_as_wifi_psk(scope: &ScopedSchema, data: &Value) -> ValidationState {
+    let mut state = validate_as_string(scope, data);
+    if !state.is_valid() {
+        return state;
+    }
+
+    let psk = data.as_str().expect("invalid validate_as_string");
+    let len = psk.chars().count();
+
+    let error = if len == HEX_PSK_LEN {
+        if psk.chars().all(|c| c.is_ascii_hexdigit()) {
+            None
+        } else {
+            Some(format!("{} characters long PSK must be hexadecimal", HEX_PSK_LEN))
+        }
+    } else if len < MIN_PASSPHRASE_LEN {
+        Some(format!(
+            "passphrase must be at least {} characters long",
+            MIN_PASSPHRASE_LEN
+        ))
+    } else if len > MAX_PASSPHRASE_LEN {
+        Some(format!(
+            "passphrase must not be longer than {} characters",
+            MAX_PASSPHRASE_LEN
+        ))
+    } else if !psk.chars().all(|c| c == ' ' || c.is_ascii_graphic()) {
+        Some("passphrase must contain printable ASCII characters only".to_string())
+    } else {
+        None
+    };
+
+    if let Some(error) = error {
+        state.push_error(scope.error("type", error));
+    }
+
+    state
+}
+
+pub fn validate_as_wifi_country(scope: &ScopedSchema, data: &Value) -> ValidationState {
+    let mut state = validate_as_string(scope, data);
+    if !state.is_valid() {
+        return state;
+    }
+
+    let country = data.as_str().expect("invalid validate_as_string");
+
+    let upper = country.to_uppercase();
+
+    if country.len() != 2 || !country.chars().all(|c| c.is_ascii_alphanumeric()) {
+        state.push_error(scope.error("type", "expected ISO 3166-1 alpha-2 country code (`US`, `DE`, ...)"));
+    } else if !COUNTRIES.contains(&upper.as_str()) {
+        state.push_error(scope.error("type", format!("unknown country code '{}'", country)));
+    } else if upper != country {
+        state.push_error(scope.error("type", format!("country code must be uppercase, expected '{}'", upper)));
+    }
+
+    state
+}